@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
 
 use anyhow::Result;
-use inflector::cases::{kebabcase::to_kebab_case, titlecase::to_title_case};
+use inflector::cases::{kebabcase::to_kebab_case, pascalcase::to_pascal_case};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use serde::Deserialize;
@@ -19,23 +19,34 @@ pub fn do_gen(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
     // Get the data from the parameters.
     let mut params = from_tokenstream::<Params>(&attr)?;
 
-    if params.tag.ends_with(":global") {
-        params.tag = params.tag.trim_end_matches(":global").to_string();
-    }
-
     // Lets get the Open API spec.
     let api = load_api_spec()?;
 
     let ops = get_operations_with_tag(&api, &params.tag)?;
 
+    // Global tags like `images:global` are about the same resource as the plain tag,
+    // only the API client tag differs.
+    params.tag = params.tag.trim_end_matches(":global").to_string();
+
+    // Tags like `ip-pools` are not valid identifiers, the API client and the
+    // operation ids use underscores instead.
+    params.tag = params.tag.replace('-', "_");
+
     let og_enum: ItemEnum = syn::parse2(item).unwrap();
     let mut variants = og_enum.variants.clone();
     let mut commands = quote!();
 
+    // Commands that are already in the enum are written by hand, so we leave them be.
+    let is_declared = |variant: &str| og_enum.variants.iter().any(|v| v.ident == variant);
+
     // Let's iterate over the paths and generate the code.
     for op in ops {
         // Let's generate the delete command if it exists.
         if op.is_root_level_operation(&params.tag) && op.method == "DELETE" {
+            if is_declared("Delete") {
+                continue;
+            }
+
             let (delete_cmd, delete_enum_item) = op.generate_delete_command(&params.tag)?;
 
             commands = quote! {
@@ -48,6 +59,10 @@ pub fn do_gen(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
             // the variants on the end.
             variants.push(delete_enum_item);
         } else if op.is_root_level_operation(&params.tag) && op.method == "GET" {
+            if is_declared("View") {
+                continue;
+            }
+
             let (view_cmd, view_enum_item) = op.generate_view_command(&params.tag)?;
 
             commands = quote! {
@@ -60,6 +75,10 @@ pub fn do_gen(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
             // the variants on the end.
             variants.push(view_enum_item);
         } else if op.is_root_level_operation(&params.tag) && op.method == "PUT" {
+            if is_declared("Edit") {
+                continue;
+            }
+
             let (edit_cmd, edit_enum_item) = op.generate_edit_command(&params.tag)?;
 
             commands = quote! {
@@ -72,6 +91,10 @@ pub fn do_gen(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
             // the variants on the end.
             variants.push(edit_enum_item);
         } else if op.is_root_create_operation(&params.tag) {
+            if is_declared("Create") {
+                continue;
+            }
+
             let (create_cmd, create_enum_item) = op.generate_create_command(&params.tag)?;
 
             commands = quote! {
@@ -84,6 +107,10 @@ pub fn do_gen(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
             // the variants on the end.
             variants.push(create_enum_item);
         } else if op.is_root_list_operation(&params.tag) {
+            if is_declared("List") {
+                continue;
+            }
+
             let (list_cmd, list_enum_item) = op.generate_list_command(&params.tag)?;
 
             commands = quote! {
@@ -577,6 +604,9 @@ struct Operation {
     #[allow(dead_code)]
     path: String,
     id: String,
+    /// The API client tag the operation lives under, like `images_global` for
+    /// operations tagged `images:global`.
+    client_tag: String,
 }
 
 struct Property {
@@ -621,6 +651,100 @@ impl Operation {
         self.id.ends_with(&format!("{}_{}", tag, self.method.to_lowercase())) && self.method == "POST"
     }
 
+    /// Returns if the resource the operation is on can be given by ID instead of by name,
+    /// in which case the names of its parents are not needed.
+    fn is_by_id_operation(&self, tag: &str) -> bool {
+        self.is_root_level_operation(tag) && get_by_id(&self.client_tag).is_some()
+    }
+
+    /// Returns the names of the parents of the resource the operation takes, like the
+    /// organization and project of an instance.
+    fn get_parent_names(&self, tag: &str) -> Vec<String> {
+        ["organization", "project", "vpc", "router"]
+            .iter()
+            .filter(|p| self.is_parameter(p) && singular(tag) != **p)
+            .map(|p| p.to_string())
+            .collect()
+    }
+
+    /// Generate the code that resolves a resource given by ID to the names of it and its
+    /// parents, and runs the command again with those.
+    fn generate_resolve_by_id(&self, tag: &str) -> TokenStream {
+        let by_id = match get_by_id(&self.client_tag) {
+            Some(by_id) if self.is_by_id_operation(tag) => by_id,
+            _ => return quote!(),
+        };
+
+        let kind = by_id.kind;
+        let singular_tag_lc = format_ident!("{}", singular(tag));
+        let parents = self
+            .get_parent_names(tag)
+            .iter()
+            .map(|p| format_ident!("{}", p))
+            .collect::<Vec<_>>();
+
+        quote! {
+            if uuid::Uuid::parse_str(&self.#singular_tag_lc).is_ok() {
+                let client = ctx.api_client("")?;
+                let path = crate::cmd_id::resolve_path(
+                    &client,
+                    crate::cmd_id::ResourceKind::#kind,
+                    &self.#singular_tag_lc,
+                )
+                .await?;
+
+                let mut cmd = self.clone();
+                cmd.#singular_tag_lc = path.name;
+                #(cmd.#parents = path.#parents;)*
+
+                return crate::cmd::Command::run(&cmd, ctx).await;
+            }
+        }
+    }
+
+    /// Generate the checks that the names of the parents of the resource were given, for
+    /// when the resource is given by name rather than by ID.
+    fn generate_check_parent_names(&self, tag: &str) -> Result<TokenStream> {
+        if !self.is_by_id_operation(tag) {
+            return Ok(quote!());
+        }
+
+        let singular_tag_str = if tag == "vpcs" {
+            singular(tag).to_uppercase()
+        } else {
+            singular(tag).replace('_', "-")
+        };
+
+        let mut checks = quote!();
+        for p in self.get_parent_names(tag) {
+            let ident = format_ident!("{}", p);
+            let error = format!(
+                "{} required when the {} is given by name",
+                get_flags(&p)?.format_help(),
+                singular_tag_str
+            );
+
+            checks = quote! {
+                #checks
+                if self.#ident.is_empty() {
+                    return Err(anyhow::anyhow!(#error));
+                }
+            };
+        }
+
+        Ok(checks)
+    }
+
+    /// Returns how the organization and project flags of the operation are required.
+    /// They are not when the resource can be given by ID instead.
+    fn get_parent_requirement(&self, tag: &str) -> TokenStream {
+        if self.is_by_id_operation(tag) {
+            quote!(default_value_t)
+        } else {
+            quote!(required = true)
+        }
+    }
+
     fn get_parameters(&self) -> Result<BTreeMap<String, Parameter>> {
         let mut parameters = BTreeMap::new();
 
@@ -794,7 +918,11 @@ impl Operation {
                 continue;
             }
 
-            p = clean_param_name(&p);
+            p = if is_resource_name_param(&p, tag) {
+                singular(tag)
+            } else {
+                clean_param_name(&p)
+            };
 
             let p = format_ident!("{}", p);
 
@@ -942,12 +1070,7 @@ impl Operation {
         required: bool,
         default: Option<serde_json::Value>,
     ) -> Result<TokenStream> {
-        if skip_defaults(name, tag)
-            || name == format!("{}_name", singular(tag))
-            || name == format!("{}_id", singular(tag))
-            || name == "limit"
-            || name == "page_token"
-        {
+        if skip_defaults(name, tag) || is_resource_name_param(name, tag) || name == "limit" || name == "page_token" {
             // Return early and empty, we don't care about these.
             return Ok(quote!());
         }
@@ -959,14 +1082,14 @@ impl Operation {
         let n = if name_cleaned == "vpc" {
             name_cleaned.to_uppercase()
         } else {
-            name_cleaned
+            name_cleaned.to_string()
         };
 
         let singular_tag = singular(tag);
         let prop = if singular_tag == "vpc" {
             singular_tag.to_uppercase()
         } else {
-            singular_tag
+            singular_tag.replace('_', "-")
         };
 
         let doc = if let Some(desc) = description {
@@ -997,7 +1120,11 @@ impl Operation {
         let short_flag = flags.get_short_token();
         let long_flag = flags.get_long_token();
 
-        let requiredq = if required {
+        // The parents of a resource that can be given by ID are only needed when it is
+        // given by name.
+        let is_by_id_parent = self.is_by_id_operation(tag) && self.get_parent_names(tag).contains(&name_cleaned);
+
+        let requiredq = if required && !is_by_id_parent {
             quote!(true)
         } else if !rendered.starts_with("Option<") {
             // Default value is meaningless for Option types.
@@ -1085,41 +1212,81 @@ impl Operation {
         }
 
         for (param, p) in self.get_request_body_properties()? {
+            if (param == "organization" || param == "project") && !self.is_parameter(&param) {
+                // Some resources can optionally be scoped to an organization or project
+                // through the request body rather than the path.
+                params.push(self.render_body_scope_param(&param, tag, p.schema, p.required)?);
+                continue;
+            }
+
             params.push(self.render_struct_param(&param, tag, p.schema, p.description, p.required, p.default)?);
         }
 
         Ok(params)
     }
 
+    /// Render an organization or project flag that is part of the request body.
+    fn render_body_scope_param(
+        &self,
+        name: &str,
+        tag: &str,
+        schema: openapiv3::ReferenceOr<openapiv3::Schema>,
+        required: bool,
+    ) -> Result<TokenStream> {
+        let name_ident = format_ident!("{}", name);
+
+        let doc = format!("The {} to scope the {} to.", name, singular(tag).replace('_', "-"));
+
+        let type_name = schema.render_type(required)?;
+
+        let flags = get_flags(name)?;
+        let short_flag = flags.get_short_token();
+        let long_flag = flags.get_long_token();
+
+        Ok(quote! {
+            #[doc = #doc]
+            #[clap(#long_flag, #short_flag default_value_t)]
+            pub #name_ident: #type_name,
+        })
+    }
+
     /// Generate the create command.
     fn generate_create_command(&self, tag: &str) -> Result<(TokenStream, syn::Variant)> {
-        let tag_ident = format_ident!("{}", tag);
+        let tag_ident = format_ident!("{}", self.client_tag);
         let singular_tag_str = if tag == "vpcs" {
             singular(tag).to_uppercase()
         } else {
-            singular(tag)
+            singular(tag).replace('_', "-")
         };
         let singular_tag_lc = format_ident!("{}", singular(tag));
-        let struct_name = format_ident!("Cmd{}Create", to_title_case(&singular(tag)));
+        let struct_name = format_ident!("Cmd{}Create", to_pascal_case(&singular(tag)));
 
         let struct_doc = format!(
             "Create a new {}.\n\nTo create a {} interactively, use `oxide {} create` with no arguments.",
             singular_tag_str,
             singular_tag_str,
-            &singular(tag)
+            singular(tag).replace('_', "-")
         );
         let struct_inner_project_doc = format!("The project that holds the {}.", singular_tag_str);
 
         let struct_inner_name_doc = format!("The name of the {} to create.", singular_tag_str);
 
         let mut mutable_variables: Vec<TokenStream> = Vec::new();
-        for (p, _) in self.get_all_required_param_names_and_types()? {
+        for (p, t) in self.get_all_required_param_names_and_types()? {
             let mut p = if p == "name" { singular(tag) } else { p };
 
             p = clean_param_name(&p);
 
             let ident = format_ident!("{}", p);
 
+            if is_always_set(&t) {
+                // We never prompt for these, so they don't need to be mutable.
+                mutable_variables.push(quote!(
+                    let #ident = self.#ident.clone();
+                ));
+                continue;
+            }
+
             mutable_variables.push(quote!(
                 let mut #ident = self.#ident.clone();
             ));
@@ -1133,7 +1300,7 @@ impl Operation {
 
             let n = clean_param_name(&p);
 
-            if n == "ipv4_block" {
+            if n == "ipv4_block" || is_always_set(&t) {
                 continue;
             }
 
@@ -1260,7 +1427,7 @@ impl Operation {
         for (p, v) in self.get_all_required_param_names_and_types()? {
             let n = clean_param_name(&p);
 
-            if skip_defaults(&n, tag) {
+            if skip_defaults(&n, tag) || is_always_set(&v) {
                 // Skip the prompt.
                 continue;
             }
@@ -1429,14 +1596,14 @@ impl Operation {
 
     /// Generate the edit command.
     fn generate_edit_command(&self, tag: &str) -> Result<(TokenStream, syn::Variant)> {
-        let tag_ident = format_ident!("{}", tag);
+        let tag_ident = format_ident!("{}", self.client_tag);
         let singular_tag_str = if tag == "vpcs" {
             singular(tag).to_uppercase()
         } else {
-            singular(tag)
+            singular(tag).replace('_', "-")
         };
         let singular_tag_lc = format_ident!("{}", singular(tag));
-        let struct_name = format_ident!("Cmd{}Edit", to_title_case(&singular(tag)));
+        let struct_name = format_ident!("Cmd{}Edit", to_pascal_case(&singular(tag)));
 
         let struct_doc = format!("Edit {} settings.", singular_tag_str,);
         let struct_inner_project_doc = format!("The project that holds the {}.", singular_tag_str);
@@ -1444,12 +1611,13 @@ impl Operation {
         let struct_inner_name_doc = format!("The {} to edit. Can be an ID or name.", singular_tag_str);
 
         let api_call_params = self.get_api_call_params(tag)?;
+        let parent_requirement = self.get_parent_requirement(tag);
 
         // We need to check if project is a parameter to this call.
         let project_param = if self.is_parameter("project") && tag != "projects" {
             quote! {
                 #[doc = #struct_inner_project_doc]
-                #[clap(long, short, required = true)]
+                #[clap(long, short, #parent_requirement)]
                 pub project: String,
             }
         } else {
@@ -1460,7 +1628,7 @@ impl Operation {
         let organization_param = if self.is_parameter("organization") && tag != "organizations" {
             quote! {
                 /// The organization that holds the project.
-                #[clap(long, short, required = true, env = "OXIDE_ORG")]
+                #[clap(long, short, #parent_requirement, env = "OXIDE_ORG")]
                 pub organization: String,
             }
         } else {
@@ -1578,6 +1746,9 @@ impl Operation {
             }
         };
 
+        let resolve_by_id = self.generate_resolve_by_id(tag);
+        let check_parent_names = self.generate_check_parent_names(tag)?;
+
         let additional_struct_params = self.get_additional_struct_params(tag)?;
 
         let cmd = quote!(
@@ -1601,6 +1772,10 @@ impl Operation {
                 async fn run(&self, ctx: &mut crate::context::Context) -> anyhow::Result<()> {
                     #check_nothing_to_edit
 
+                    #resolve_by_id
+
+                    #check_parent_names
+
                     let client = ctx.api_client("")?;
 
                     let mut name = self.#singular_tag_lc.clone();
@@ -1628,14 +1803,14 @@ impl Operation {
 
     /// Generate the view command.
     fn generate_view_command(&self, tag: &str) -> Result<(TokenStream, syn::Variant)> {
-        let tag_ident = format_ident!("{}", tag);
+        let tag_ident = format_ident!("{}", self.client_tag);
         let singular_tag_str = if tag == "vpcs" {
             singular(tag).to_uppercase()
         } else {
-            singular(tag)
+            singular(tag).replace('_', "-")
         };
         let singular_tag_lc = format_ident!("{}", singular(tag));
-        let struct_name = format_ident!("Cmd{}View", to_title_case(&singular(tag)));
+        let struct_name = format_ident!("Cmd{}View", to_pascal_case(&singular(tag)));
 
         let struct_doc = format!(
             "View {}.\n\nDisplay information about an Oxide {}.\n\nWith `--web`, open the {} in a web browser instead.",
@@ -1647,12 +1822,13 @@ impl Operation {
         let struct_inner_name_doc = format!("The {} to view. Can be an ID or name.", singular_tag_str);
 
         let api_call_params = self.get_api_call_params(tag)?;
+        let parent_requirement = self.get_parent_requirement(tag);
 
         // We need to check if project is a parameter to this call.
         let project_param = if self.is_parameter("project") && tag != "projects" {
             quote! {
                 #[doc = #struct_inner_project_doc]
-                #[clap(long, short, required = true)]
+                #[clap(long, short, #parent_requirement)]
                 pub project: String,
             }
         } else {
@@ -1663,7 +1839,7 @@ impl Operation {
         let organization_param = if self.is_parameter("organization") && tag != "organizations" {
             quote! {
                 /// The organization that holds the project.
-                #[clap(long, short, required = true, env = "OXIDE_ORG")]
+                #[clap(long, short, #parent_requirement, env = "OXIDE_ORG")]
                 pub organization: String,
             }
         } else {
@@ -1672,6 +1848,27 @@ impl Operation {
 
         let additional_struct_params = self.get_additional_struct_params(tag)?;
 
+        // Lookups by ID don't need the names of the parents of the resource.
+        let result = if let Some(by_id) = get_by_id(&self.client_tag).filter(|_| self.is_by_id_operation(tag)) {
+            let client_tag = by_id.client_tag;
+            let function = by_id.function;
+            let check_parent_names = self.generate_check_parent_names(tag)?;
+
+            quote! {
+                let result = if uuid::Uuid::parse_str(&self.#singular_tag_lc).is_ok() {
+                    client.#client_tag().#function(&self.#singular_tag_lc).await?
+                } else {
+                    #check_parent_names
+
+                    client.#tag_ident().get(#(#api_call_params),*).await?
+                };
+            }
+        } else {
+            quote! {
+                let result = client.#tag_ident().get(#(#api_call_params),*).await?;
+            }
+        };
+
         let cmd = quote!(
             #[doc = #struct_doc]
             #[derive(clap::Parser, Debug, Clone)]
@@ -1713,7 +1910,7 @@ impl Operation {
 
                     let client = ctx.api_client("")?;
 
-                    let result = client.#tag_ident().get(#(#api_call_params),*).await?;
+                    #result
 
                     let format = ctx.format(&self.format)?;
                     ctx.io.write_output(&format, &result)?;
@@ -1732,13 +1929,13 @@ impl Operation {
 
     /// Generate the list command.
     fn generate_list_command(&self, tag: &str) -> Result<(TokenStream, syn::Variant)> {
-        let tag_ident = format_ident!("{}", tag);
+        let tag_ident = format_ident!("{}", self.client_tag);
         let singular_tag_str = if tag == "vpcs" {
             singular(tag).to_uppercase()
         } else {
-            singular(tag)
+            singular(tag).replace('_', "-")
         };
-        let struct_name = format_ident!("Cmd{}List", to_title_case(&singular(tag)));
+        let struct_name = format_ident!("Cmd{}List", to_pascal_case(&singular(tag)));
 
         let struct_doc = format!("List {}.", plural(&singular_tag_str));
         let struct_inner_project_doc = format!("The project that holds the {}.", plural(&singular_tag_str));
@@ -1756,7 +1953,11 @@ impl Operation {
                 continue;
             }
 
-            let n = clean_param_name(&p);
+            let n = if is_resource_name_param(&p, tag) {
+                singular(tag)
+            } else {
+                clean_param_name(&p)
+            };
             let ident = format_ident!("{}", n);
 
             api_call_params_all.push(quote!(&self.#ident));
@@ -1849,26 +2050,27 @@ impl Operation {
 
     /// Generate the delete command.
     fn generate_delete_command(&self, tag: &str) -> Result<(TokenStream, syn::Variant)> {
-        let tag_ident = format_ident!("{}", tag);
+        let tag_ident = format_ident!("{}", self.client_tag);
         let singular_tag_str = if tag == "vpcs" {
             singular(tag).to_uppercase()
         } else {
-            singular(tag)
+            singular(tag).replace('_', "-")
         };
         let singular_tag_lc = format_ident!("{}", singular(tag));
-        let struct_name = format_ident!("Cmd{}Delete", to_title_case(&singular(tag)));
+        let struct_name = format_ident!("Cmd{}Delete", to_pascal_case(&singular(tag)));
 
         let struct_doc = format!("Delete {}.", singular_tag_str);
         let struct_inner_name_doc = format!("The {} to delete. Can be an ID or name.", singular_tag_str);
         let struct_inner_project_doc = format!("The project to delete the {} from.", singular_tag_str);
 
         let api_call_params = self.get_api_call_params(tag)?;
+        let parent_requirement = self.get_parent_requirement(tag);
 
         // We need to check if project is a parameter to this call.
         let project_param = if self.is_parameter("project") && tag != "projects" {
             quote! {
                 #[doc = #struct_inner_project_doc]
-                #[clap(long, short, required = true)]
+                #[clap(long, short, #parent_requirement)]
                 pub project: String,
             }
         } else {
@@ -1879,13 +2081,16 @@ impl Operation {
         let organization_param = if self.is_parameter("organization") && tag != "organizations" {
             quote! {
                 /// The organization that holds the project.
-                #[clap(long, short, required = true, env = "OXIDE_ORG")]
+                #[clap(long, short, #parent_requirement, env = "OXIDE_ORG")]
                 pub organization: String,
             }
         } else {
             quote!()
         };
 
+        let resolve_by_id = self.generate_resolve_by_id(tag);
+        let check_parent_names = self.generate_check_parent_names(tag)?;
+
         let additional_struct_params = self.get_additional_struct_params(tag)?;
 
         // We need to form the output back to the client.
@@ -1956,6 +2161,10 @@ impl Operation {
                         return Err(anyhow::anyhow!("--confirm required when not running interactively"));
                     }
 
+                    #resolve_by_id
+
+                    #check_parent_names
+
                     let client = ctx.api_client("")?;
 
 
@@ -2019,6 +2228,7 @@ fn get_operations_with_tag(api: &openapiv3::OpenAPI, tag: &str) -> Result<Vec<Op
                             method: m.to_string(),
                             path: pn.to_string(),
                             id,
+                            client_tag: tag.replace([':', '-'], "_"),
                         }]);
                     }
                 }
@@ -2071,6 +2281,58 @@ fn skip_defaults(n: &str, tag: &str) -> bool {
         || n == "name"
 }
 
+/// Returns if a parameter of the given type always holds a value, even when it
+/// was not passed, so there is nothing to check or prompt for.
+/// Bools are flags and string enums fall back to their default variant.
+fn is_always_set(schema: &openapiv3::ReferenceOr<openapiv3::Schema>) -> bool {
+    let schema = match schema.get_schema_from_reference(true) {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+
+    match &schema.schema_kind {
+        openapiv3::SchemaKind::Type(openapiv3::Type::Boolean {}) => true,
+        openapiv3::SchemaKind::Type(openapiv3::Type::String(st)) => !st.enumeration.is_empty(),
+        _ => false,
+    }
+}
+
+/// How to look up a resource of a tag by its ID.
+struct ById {
+    /// The `crate::cmd_id::ResourceKind` of the resource.
+    kind: proc_macro2::Ident,
+    /// The API client tag with the endpoint.
+    client_tag: proc_macro2::Ident,
+    /// The API client function for the endpoint.
+    function: proc_macro2::Ident,
+}
+
+/// Returns how to look up a resource of the tag by its ID, if the API has an
+/// endpoint for it.
+/// The endpoints for VPC subnets, routers and routes all live under the `vpcs` tag.
+fn get_by_id(tag: &str) -> Option<ById> {
+    let (kind, client_tag, function) = match tag {
+        "disks" => ("Disk", "disks", "view_by_id"),
+        "images" => ("Image", "images", "view_by_id"),
+        "images_global" => ("GlobalImage", "images_global", "view_by_id"),
+        "instances" => ("Instance", "instances", "view_by_id"),
+        "organizations" => ("Organization", "organizations", "view_by_id"),
+        "projects" => ("Project", "projects", "view_by_id"),
+        "snapshots" => ("Snapshot", "snapshots", "view_by_id"),
+        "vpcs" => ("Vpc", "vpcs", "view_by_id"),
+        "subnets" => ("Subnet", "vpcs", "subnet_view_by_id"),
+        "routers" => ("Router", "vpcs", "router_view_by_id"),
+        "routes" => ("Route", "vpcs", "router_route_view_by_id"),
+        _ => return None,
+    };
+
+    Some(ById {
+        kind: format_ident!("{}", kind),
+        client_tag: format_ident!("{}", client_tag),
+        function: format_ident!("{}", function),
+    })
+}
+
 fn clean_text(s: &str) -> String {
     // Add newlines after end-braces at <= two levels of indentation.
     if cfg!(not(windows)) {
@@ -2095,6 +2357,17 @@ pub fn get_text_fmt(output: &proc_macro2::TokenStream) -> Result<String> {
     Ok(clean_text(&content))
 }
 
+/// Returns if the given parameter is the name (or id) of the resource the tag is for.
+///
+/// Most paths use the full singular tag, like `instance_name` for `instances`, but
+/// some only use the last word of it, like `pool_name` for `ip_pools`.
+fn is_resource_name_param(p: &str, tag: &str) -> bool {
+    let singular_tag = singular(tag);
+    let last_word = singular_tag.rsplit('_').next().unwrap_or_default();
+
+    p == format!("{}_name", singular_tag) || p == format!("{}_id", singular_tag) || p == format!("{}_name", last_word)
+}
+
 fn clean_param_name(p: &str) -> String {
     if p != "new_name" && !p.ends_with("dns_name") {
         p.trim_end_matches("_name").trim_end_matches("_id").to_string()
@@ -131,10 +131,57 @@ fn test_do_gen() {
         },
         quote! {
             #[derive(Parser, Debug, Clone)]
-            enum SubCommand {}
+            enum SubCommand {
+                Create(CmdImageCreate),
+            }
         },
     )
     .unwrap();
 
     expectorate::assert_contents("tests/gen/images_global.rs.gen", &get_text_fmt(&actual).unwrap());
+
+    actual = do_gen(
+        quote! {
+            tag = "ip-pools",
+        },
+        quote! {
+            #[derive(Parser, Debug, Clone)]
+            enum SubCommand {
+                Range(CmdIpPoolRange),
+            }
+        },
+    )
+    .unwrap();
+
+    expectorate::assert_contents("tests/gen/ip_pools.rs.gen", &get_text_fmt(&actual).unwrap());
+
+    actual = do_gen(
+        quote! {
+            tag = "silos",
+        },
+        quote! {
+            #[derive(Parser, Debug, Clone)]
+            enum SubCommand {
+                Idp(CmdSiloIdp),
+            }
+        },
+    )
+    .unwrap();
+
+    expectorate::assert_contents("tests/gen/silos.rs.gen", &get_text_fmt(&actual).unwrap());
+
+    actual = do_gen(
+        quote! {
+            tag = "sagas",
+        },
+        quote! {
+            #[derive(Parser, Debug, Clone)]
+            enum SubCommand {
+                Watch(CmdSagaWatch),
+            }
+        },
+    )
+    .unwrap();
+
+    expectorate::assert_contents("tests/gen/sagas.rs.gen", &get_text_fmt(&actual).unwrap());
 }
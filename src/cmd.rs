@@ -15,3 +15,27 @@ pub trait Command {
 /*pub trait CommandExamples {
     fn examples(&self) -> Vec<Example>;
 }*/
+
+/// Returns the HTTP status of an error returned by the API, or `None` if the error
+/// didn't come from the API.
+///
+/// Errors the API client parsed only map to the statuses its `Error` enum models:
+/// 400, 401, 403, 404, 405, 500 and 503. Others, like 409, are never returned for them.
+pub fn api_error_status(err: &anyhow::Error) -> Option<http::StatusCode> {
+    if let Some(err) = err.downcast_ref::<oxide_api::types::Error>() {
+        // These are defined here: https://github.com/oxidecomputer/omicron/blob/main/common/src/api/external/error.rs#L28
+        return Some(match err {
+            oxide_api::types::Error::ObjectNotFound { .. } => http::StatusCode::NOT_FOUND,
+            oxide_api::types::Error::ObjectAlreadyExists { .. }
+            | oxide_api::types::Error::InvalidRequest { .. }
+            | oxide_api::types::Error::InvalidValue { .. } => http::StatusCode::BAD_REQUEST,
+            oxide_api::types::Error::Unauthenticated { .. } => http::StatusCode::UNAUTHORIZED,
+            oxide_api::types::Error::Forbidden => http::StatusCode::FORBIDDEN,
+            oxide_api::types::Error::MethodNotAllowed { .. } => http::StatusCode::METHOD_NOT_ALLOWED,
+            oxide_api::types::Error::InternalError { .. } => http::StatusCode::INTERNAL_SERVER_ERROR,
+            oxide_api::types::Error::ServiceUnavailable { .. } => http::StatusCode::SERVICE_UNAVAILABLE,
+        });
+    }
+
+    err.downcast_ref::<reqwest::Error>().and_then(|err| err.status())
+}
@@ -99,6 +99,12 @@ fn parse_host_interactively(ctx: &mut crate::context::Context) -> Result<url::Ur
 ///
 /// Alternatively, pass in a token on standard input by using `--with-token`.
 ///
+/// To log in to a silo with a username and password instead of through a browser,
+/// pass `--silo` and `--username`, and `--provider` if the silo's identity provider
+/// is not called `local`. The password is prompted for, or read from standard input
+/// when not running interactively. The session the silo gives you is kept for the
+/// host, and used to authorize a token for the CLI like the browser would.
+///
 ///     # start interactive setup
 ///     $ oxide auth login
 ///
@@ -110,13 +116,28 @@ fn parse_host_interactively(ctx: &mut crate::context::Context) -> Result<url::Ur
 ///
 ///     # authenticate with an insecure Oxide instance (not recommended)
 ///     $ oxide auth login --host http://oxide.internal
+///
+///     # authenticate as a user of a silo, reading the password from a file
+///     $ oxide auth login --host oxide.internal --silo lab --username admin < password.txt
 #[derive(Parser, Debug, Clone)]
 #[clap(verbatim_doc_comment)]
 pub struct CmdAuthLogin {
     /// Read token from standard input.
-    #[clap(long)]
+    #[clap(long, conflicts_with = "username")]
     pub with_token: bool,
 
+    /// The silo to log in to with a username and password.
+    #[clap(long, requires = "username")]
+    pub silo: Option<String>,
+
+    /// The username to log in to the silo with.
+    #[clap(short, long, requires = "silo")]
+    pub username: Option<String>,
+
+    /// The identity provider of the silo to log in with a username and password.
+    #[clap(long, default_value = "local")]
+    pub provider: String,
+
     /// The host of the Oxide instance to authenticate with.
     /// This assumes the instance is an `https://` url, if not otherwise specified
     /// as `http://`.
@@ -131,7 +152,7 @@ pub struct CmdAuthLogin {
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdAuthLogin {
     async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
-        if !ctx.io.can_prompt() && !self.with_token {
+        if !ctx.io.can_prompt() && !self.with_token && self.username.is_none() {
             return Err(anyhow!("--with-token required when not running interactively"));
         }
 
@@ -204,6 +225,12 @@ impl crate::cmd::Command for CmdAuthLogin {
                 }
             }
 
+            let password = if let Some(username) = &self.username {
+                Some(read_password(ctx, username)?)
+            } else {
+                None
+            };
+
             // Do an OAuth 2.0 Device Authorization Grant dance to get a token.
             let device_auth_url = DeviceAuthorizationUrl::new(format!("{}device/auth", host))?;
             let client_id = ctx.config.get("", "client_id")?;
@@ -221,7 +248,17 @@ impl crate::cmd::Command for CmdAuthLogin {
                 .request_async(async_http_client)
                 .await?;
 
-            if let Some(uri) = details.verification_uri_complete() {
+            if let (Some(username), Some(password)) = (&self.username, &password) {
+                let silo = self.silo.as_deref().unwrap_or_default();
+                let session = login_with_password(ctx, host, silo, &self.provider, username, password).await?;
+
+                // Keep the session for the host, so it can be ended on logout.
+                ctx.config.set(host, "session", &session)?;
+
+                // Confirm the user code ourselves with the session, instead of having
+                // someone do it in a browser.
+                confirm_device(ctx, host, &session, details.user_code().secret()).await?;
+            } else if let Some(uri) = details.verification_uri_complete() {
                 writeln!(
                     ctx.io.out,
                     "Opening {} in your browser.\n\
@@ -258,9 +295,7 @@ impl crate::cmd::Command for CmdAuthLogin {
         let session = client.hidden().session_me().await?;
 
         // Set the user.
-        // TODO: This should instead store the email, or some username or something
-        // that is human knowable.
-        let email = session.id;
+        let email = crate::cmd_user::display_name(&session);
         ctx.config.set(host, "user", &email)?;
 
         // Save the config.
@@ -272,6 +307,129 @@ impl crate::cmd::Command for CmdAuthLogin {
     }
 }
 
+/// Prompt for the password of the user, or read it from standard input when not
+/// running interactively.
+fn read_password(ctx: &mut crate::context::Context, username: &str) -> Result<String> {
+    if ctx.io.can_prompt() {
+        return match dialoguer::Password::new()
+            .with_prompt(format!("Password for {}", username))
+            .interact()
+        {
+            Ok(password) => Ok(password),
+            Err(err) => Err(anyhow!("prompt failed: {}", err)),
+        };
+    }
+
+    let mut password = String::new();
+    ctx.io.stdin.read_to_string(&mut password)?;
+    let password = password.trim_end_matches('\n').to_string();
+    if password.is_empty() {
+        return Err(anyhow!("no password given on standard input"));
+    }
+
+    Ok(password)
+}
+
+/// Log in to the silo with the username and password through its identity provider,
+/// returning the session cookie it sets.
+async fn login_with_password(
+    ctx: &crate::context::Context<'_>,
+    host: &str,
+    silo: &str,
+    provider: &str,
+    username: &str,
+    password: &str,
+) -> Result<String> {
+    let client = ctx.anonymous_api_client(host);
+
+    // The body is passed on to the identity provider as is, which is why the API
+    // takes it as an octet stream. Username and password providers read it as JSON.
+    let body = serde_json::to_vec(&serde_json::json!({
+        "username": username,
+        "password": password,
+    }))?;
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(
+        reqwest::header::CONTENT_TYPE,
+        reqwest::header::HeaderValue::from_static("application/octet-stream"),
+    );
+
+    let resp = client
+        .request_raw(
+            http::method::Method::POST,
+            &format!("/login/{}/{}", silo, provider),
+            Some(reqwest::Body::from(body)),
+        )
+        .await?
+        .headers(headers)
+        .send()
+        .await?;
+    if !resp.status().is_success() {
+        return Err(anyhow!(
+            "logging in to silo {} as {} failed: {}",
+            silo,
+            username,
+            resp.status()
+        ));
+    }
+
+    session_cookie(resp.headers()).ok_or_else(|| anyhow!("logging in to silo {} did not return a session", silo))
+}
+
+/// Confirm the device authorization user code as the user of the session.
+async fn confirm_device(ctx: &crate::context::Context<'_>, host: &str, session: &str, user_code: &str) -> Result<()> {
+    let client = ctx.anonymous_api_client(host);
+
+    let body = serde_json::to_vec(&serde_json::json!({ "user_code": user_code }))?;
+    let resp = client
+        .request_raw(
+            http::method::Method::POST,
+            "/device/confirm",
+            Some(reqwest::Body::from(body)),
+        )
+        .await?
+        .header(reqwest::header::COOKIE, session)
+        .send()
+        .await?;
+    if !resp.status().is_success() {
+        return Err(anyhow!("confirming the device authorization failed: {}", resp.status()));
+    }
+
+    Ok(())
+}
+
+/// End the session from a username and password login to the host, if there is one.
+async fn end_session(ctx: &crate::context::Context<'_>, host: &str) {
+    let session = match ctx.config.get(host, "session") {
+        Ok(session) if !session.is_empty() => session,
+        _ => return,
+    };
+
+    // Failing to end the session is not worth failing the logout over, it expires on
+    // its own.
+    if let Ok(req) = ctx
+        .anonymous_api_client(host)
+        .request_raw(http::method::Method::POST, "/logout", None)
+        .await
+    {
+        let _ = req.header(reqwest::header::COOKIE, session).send().await;
+    }
+}
+
+/// Returns the `session` cookie set by the response headers, ready to be sent back in
+/// a `Cookie` header.
+fn session_cookie(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    headers
+        .get_all(reqwest::header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .filter_map(|v| v.split(';').next())
+        .map(|v| v.trim())
+        .find(|v| v.starts_with("session="))
+        .map(|v| v.to_string())
+}
+
 /// Log out of an Oxide host.
 ///
 /// This command removes the authentication configuration for a host either specified
@@ -358,9 +516,7 @@ impl crate::cmd::Command for CmdAuthLogout {
         // Get the current user.
         let session = client.hidden().session_me().await?;
 
-        // TODO: this should be the users email or something better.
-        // make it consistent with login.
-        let email = session.id;
+        let email = crate::cmd_user::display_name(&session);
 
         if ctx.io.can_prompt() {
             match dialoguer::Confirm::new()
@@ -377,6 +533,8 @@ impl crate::cmd::Command for CmdAuthLogout {
             }
         }
 
+        end_session(ctx, &hostname).await;
+
         // Unset the host.
         ctx.config.unset_host(&hostname)?;
 
@@ -448,9 +606,7 @@ impl crate::cmd::Command for CmdAuthStatus {
 
             match client.hidden().session_me().await {
                 Ok(session) => {
-                    // TODO: this should be the users email or something consistent with login
-                    // and logout.
-                    let email = session.id.to_string();
+                    let email = crate::cmd_user::display_name(&session);
                     // Let the user know if their token is invalid.
                     /*if !session.is_valid() {
                     host_status.push(format!(
@@ -558,6 +714,9 @@ mod test {
                 cmd: crate::cmd_auth::SubCommand::Login(crate::cmd_auth::CmdAuthLogin {
                     host: Some(test_host.clone()),
                     with_token: false,
+                    silo: None,
+                    username: None,
+                    provider: "local".to_string(),
                 }),
                 stdin: test_token.to_string(),
                 want_out: "".to_string(),
@@ -568,6 +727,9 @@ mod test {
                 cmd: crate::cmd_auth::SubCommand::Login(crate::cmd_auth::CmdAuthLogin {
                     host: Some(test_host.clone()),
                     with_token: true,
+                    silo: None,
+                    username: None,
+                    provider: "local".to_string(),
                 }),
                 stdin: test_token.to_string(),
                 want_out: "✔ Logged in as ".to_string(),
@@ -707,4 +869,19 @@ mod test {
             Ok(host) if host == "http://example.com:8888/"
         ));
     }
+
+    #[test]
+    fn test_session_cookie() {
+        use reqwest::header::{HeaderMap, HeaderValue, SET_COOKIE};
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(super::session_cookie(&headers), None);
+
+        headers.append(SET_COOKIE, HeaderValue::from_static("other=1; Path=/"));
+        headers.append(
+            SET_COOKIE,
+            HeaderValue::from_static("session=abc123; Path=/; HttpOnly; SameSite=Lax; Max-Age=3600"),
+        );
+        assert_eq!(super::session_cookie(&headers), Some("session=abc123".to_string()));
+    }
 }
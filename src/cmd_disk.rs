@@ -1,12 +1,13 @@
 use std::io::Write;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::Parser;
 use cli_macro::crud_gen;
+use parse_display::{Display, FromStr};
 
 /// Create, list, edit, view, and delete disks.
 ///
-/// Additionally, attach and detach disks to an instance.
+/// Additionally, attach and detach disks to an instance, and show the metrics of a disk.
 #[derive(Parser, Debug, Clone)]
 #[clap(verbatim_doc_comment)]
 pub struct CmdDisk {
@@ -22,6 +23,7 @@ enum SubCommand {
     Attach(CmdDiskAttach),
     Detach(CmdDiskDetach),
     Edit(CmdDiskEdit),
+    Metrics(CmdDiskMetrics),
 }
 
 #[async_trait::async_trait]
@@ -34,6 +36,7 @@ impl crate::cmd::Command for CmdDisk {
             SubCommand::Detach(cmd) => cmd.run(ctx).await,
             SubCommand::Edit(cmd) => cmd.run(ctx).await,
             SubCommand::List(cmd) => cmd.run(ctx).await,
+            SubCommand::Metrics(cmd) => cmd.run(ctx).await,
             SubCommand::View(cmd) => cmd.run(ctx).await,
         }
     }
@@ -54,31 +57,32 @@ pub struct CmdDiskAttach {
     instance: String,
 
     /// The project that holds the disk and instance.
-    #[clap(long, short, required = true)]
+    #[clap(long, short, default_value_t)]
     pub project: String,
 
     /// The organization that holds the project.
-    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
     pub organization: String,
 }
 
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdDiskAttach {
     async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let (disk, instance, organization, project) =
+            resolve_disk_and_instance(ctx, &self.disk, &self.instance, &self.organization, &self.project).await?;
+
         let client = ctx.api_client("")?;
 
-        let full_name = format!("{}/{}", self.organization, self.project);
+        let full_name = format!("{}/{}", organization, project);
 
         // Attach the disk.
         client
             .instances()
             .disks_attach(
-                &self.instance,
-                &self.organization,
-                &self.project,
-                &oxide_api::types::DiskIdentifier {
-                    name: self.disk.to_string(),
-                },
+                &instance,
+                &organization,
+                &project,
+                &oxide_api::types::DiskIdentifier { name: disk.to_string() },
             )
             .await?;
 
@@ -87,8 +91,8 @@ impl crate::cmd::Command for CmdDiskAttach {
             ctx.io.out,
             "{} Attached disk {} to instance {} in project {}",
             cs.success_icon(),
-            self.disk,
-            self.instance,
+            disk,
+            instance,
             full_name
         )?;
 
@@ -109,31 +113,32 @@ pub struct CmdDiskDetach {
     instance: String,
 
     /// The project that holds the disk and instance.
-    #[clap(long, short, required = true)]
+    #[clap(long, short, default_value_t)]
     pub project: String,
 
     /// The organization that holds the project.
-    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
     pub organization: String,
 }
 
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdDiskDetach {
     async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let (disk, instance, organization, project) =
+            resolve_disk_and_instance(ctx, &self.disk, &self.instance, &self.organization, &self.project).await?;
+
         let client = ctx.api_client("")?;
 
-        let full_name = format!("{}/{}", self.organization, self.project);
+        let full_name = format!("{}/{}", organization, project);
 
         // Detach the disk.
         client
             .instances()
             .disks_detach(
-                &self.instance,
-                &self.organization,
-                &self.project,
-                &oxide_api::types::DiskIdentifier {
-                    name: self.disk.to_string(),
-                },
+                &instance,
+                &organization,
+                &project,
+                &oxide_api::types::DiskIdentifier { name: disk.to_string() },
             )
             .await?;
 
@@ -142,8 +147,8 @@ impl crate::cmd::Command for CmdDiskDetach {
             ctx.io.out,
             "{} Detached disk {} from instance {} in project {}",
             cs.success_icon_with_color(ansi_term::Color::Red),
-            self.disk,
-            self.instance,
+            disk,
+            instance,
             full_name
         )?;
 
@@ -151,18 +156,489 @@ impl crate::cmd::Command for CmdDiskDetach {
     }
 }
 
+/// Resolve a disk and an instance that may be given by ID to their names, along with the
+/// organization and project that hold them both.
+async fn resolve_disk_and_instance(
+    ctx: &mut crate::context::Context,
+    disk: &str,
+    instance: &str,
+    organization: &str,
+    project: &str,
+) -> Result<(String, String, String, String)> {
+    let disk_path = crate::cmd_id::resolve_if_id(ctx, crate::cmd_id::ResourceKind::Disk, disk).await?;
+    let instance_path = crate::cmd_id::resolve_if_id(ctx, crate::cmd_id::ResourceKind::Instance, instance).await?;
+
+    let (organization, project) = match (&disk_path, &instance_path) {
+        (Some(d), Some(i)) if d.organization != i.organization || d.project != i.project => {
+            return Err(anyhow!(
+                "disk {} is in {}/{} but instance {} is in {}/{}",
+                d.name,
+                d.organization,
+                d.project,
+                i.name,
+                i.organization,
+                i.project
+            ));
+        }
+        (Some(p), _) | (None, Some(p)) => (p.organization.to_string(), p.project.to_string()),
+        (None, None) => {
+            crate::cmd_id::require_project(crate::cmd_id::ResourceKind::Disk, organization, project)?;
+            (organization.to_string(), project.to_string())
+        }
+    };
+
+    Ok((
+        disk_path.map_or_else(|| disk.to_string(), |p| p.name),
+        instance_path.map_or_else(|| instance.to_string(), |p| p.name),
+        organization,
+        project,
+    ))
+}
+
 /// Edit disk settings.
+///
+/// With `--editor`, the disk is opened as YAML in your editor and the fields you
+/// change are applied once you save and exit. With `--from-file`, the edited disk is
+/// read from a YAML or JSON file instead, or from standard input if the file is `-`.
+/// Fields left out are left as they are.
+///
+/// The API only allows changing the `state` of a disk, to `attached` to an instance
+/// given by ID, or to `detached`, which attaches or detaches it. A disk attached to
+/// another instance is detached from it first, and attached to it again if attaching
+/// it to the new instance fails. Edits to any other field, including `name` and
+/// `description`, are refused before anything is changed.
+///
+///     # attach a disk to an instance
+///     $ oxide disk edit my-disk -F state.yaml -o foo -p bar
+///     $ cat state.yaml
+///     state:
+///       state: attached
+///       instance: 4b0a4a5e-7d8c-4a3c-9a6f-2f1d6c1e0a01
 #[derive(Parser, Debug, Clone)]
 #[clap(verbatim_doc_comment)]
-pub struct CmdDiskEdit {}
+pub struct CmdDiskEdit {
+    /// The disk to edit. Can be an ID or name.
+    #[clap(name = "disk", required = true)]
+    disk: String,
+
+    /// The project that holds the disk.
+    #[clap(long, short, default_value_t)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// Read the edited disk from a YAML or JSON file, or `-` for standard input.
+    #[clap(long, short = 'F', default_value_t, conflicts_with = "editor")]
+    pub from_file: String,
+
+    /// Edit the disk in the configured editor.
+    #[clap(long)]
+    pub editor: bool,
+}
+
+impl crate::cmd_id::ByNameOrId for CmdDiskEdit {
+    const KIND: crate::cmd_id::ResourceKind = crate::cmd_id::ResourceKind::Disk;
+
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String) {
+        (&mut self.disk, &mut self.organization, &mut self.project)
+    }
+}
 
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdDiskEdit {
-    async fn run(&self, _ctx: &mut crate::context::Context) -> Result<()> {
-        todo!()
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if self.from_file.is_empty() && !self.editor {
+            return Err(anyhow!("--from-file or --editor required"));
+        }
+
+        if self.editor && !ctx.io.can_prompt() {
+            return Err(anyhow!("--editor requires a terminal, use --from-file instead"));
+        }
+
+        if let Some(cmd) = crate::cmd_id::by_name(ctx, self).await? {
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
+        let client = ctx.api_client("")?;
+
+        let disk = client
+            .disks()
+            .get(&self.disk, &self.organization, &self.project)
+            .await?;
+
+        let current = serde_json::to_value(&disk)?;
+        let edited = crate::edit::read_edited(ctx, &disk, &self.from_file)?;
+        let changed = crate::edit::changed_fields(&current, &edited)?;
+        if changed.is_empty() {
+            let cs = ctx.io.color_scheme();
+            writeln!(ctx.io.out, "{} No changes to disk {}", cs.warning_icon(), self.disk)?;
+            return Ok(());
+        }
+
+        crate::edit::refuse_uneditable("disk", &changed, &["state"])?;
+
+        // The instance the disk is attached to now, if any.
+        let attached_to = current["state"]["instance"].as_str().map(|i| i.to_string());
+
+        let state = edited
+            .get("state")
+            .ok_or_else(|| anyhow!("state is missing"))?
+            .get("state")
+            .ok_or_else(|| anyhow!("state.state is missing"))?;
+        let attach_to = match state.as_str() {
+            Some("attached") => match edited["state"]["instance"].as_str() {
+                Some(instance) => Some(instance.to_string()),
+                None => return Err(anyhow!("state.instance is required to attach the disk")),
+            },
+            Some("detached") => None,
+            _ => {
+                return Err(anyhow!(
+                    "state can only be edited to attached or detached, not {}",
+                    state
+                ))
+            }
+        };
+
+        if let Some(instance) = &attached_to {
+            let cmd = CmdDiskDetach {
+                disk: self.disk.to_string(),
+                instance: instance.to_string(),
+                organization: self.organization.to_string(),
+                project: self.project.to_string(),
+            };
+            crate::cmd::Command::run(&cmd, ctx).await?;
+        }
+
+        if let Some(instance) = attach_to {
+            let cmd = CmdDiskAttach {
+                disk: self.disk.to_string(),
+                instance: instance.to_string(),
+                organization: self.organization.to_string(),
+                project: self.project.to_string(),
+            };
+            if let Err(err) = crate::cmd::Command::run(&cmd, ctx).await {
+                // Put the disk back where it was, rather than leave it detached.
+                let original = match attached_to {
+                    Some(original) => original,
+                    None => return Err(err),
+                };
+
+                let cmd = CmdDiskAttach {
+                    disk: self.disk.to_string(),
+                    instance: original.to_string(),
+                    organization: self.organization.to_string(),
+                    project: self.project.to_string(),
+                };
+                return match crate::cmd::Command::run(&cmd, ctx).await {
+                    Ok(()) => Err(anyhow!(
+                        "attaching disk {} to instance {} failed, it was attached to instance {} again: {}",
+                        self.disk,
+                        instance,
+                        original,
+                        err
+                    )),
+                    Err(reattach_err) => Err(anyhow!(
+                        "attaching disk {} to instance {} failed: {}, and attaching it to instance {} again \
+                         failed too, so it is left detached: {}",
+                        self.disk,
+                        instance,
+                        err,
+                        original,
+                        reattach_err
+                    )),
+                };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Show the metrics of a disk.
+///
+/// The samples of the metric between `--since` and `--until` are fetched. Both take
+/// `now`, an RFC 3339 timestamp like `2022-08-01T12:00:00Z`, or a duration before
+/// now like `30m` or `1d12h`.
+///
+/// With `--chart`, the samples are drawn as a sparkline or a bar chart sized to the
+/// terminal. Counters, like `read` or `write_bytes`, are drawn as the change between
+/// samples rather than their running total.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdDiskMetrics {
+    /// The disk to show the metrics of. Can be an ID or name.
+    #[clap(name = "disk", required = true)]
+    pub disk: String,
+
+    /// The metric to show.
+    #[clap(name = "metric", required = true)]
+    pub metric: oxide_api::types::DiskMetricName,
+
+    /// The project that holds the disk.
+    #[clap(long, short, default_value_t)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// The start of the time range.
+    #[clap(long, short, default_value = "1h")]
+    pub since: crate::types::TimeSpec,
+
+    /// The end of the time range.
+    #[clap(long, short, default_value = "now")]
+    pub until: crate::types::TimeSpec,
+
+    /// Draw the samples as a chart, either a sparkline or bars.
+    #[clap(long, short, conflicts_with = "format")]
+    pub chart: Option<ChartStyle>,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+/// The style of chart to draw metrics with.
+#[derive(PartialEq, Eq, Debug, Clone, FromStr, Display)]
+#[display(style = "kebab-case")]
+pub enum ChartStyle {
+    /// A single line of block characters.
+    Sparkline,
+    /// A horizontal bar per bucket of samples.
+    Bars,
+}
+
+/// A single sample of a disk metric.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, tabled::Tabled)]
+pub struct DiskMetricSample {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub value: f64,
+}
+
+impl crate::cmd_id::ByNameOrId for CmdDiskMetrics {
+    const KIND: crate::cmd_id::ResourceKind = crate::cmd_id::ResourceKind::Disk;
+
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String) {
+        (&mut self.disk, &mut self.organization, &mut self.project)
     }
 }
 
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdDiskMetrics {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let now = chrono::Utc::now();
+        let start = self.since.resolve(now)?;
+        let end = self.until.resolve(now)?;
+        if start >= end {
+            return Err(anyhow!("--since must be before --until"));
+        }
+
+        if let Some(cmd) = crate::cmd_id::by_name(ctx, self).await? {
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
+        let client = ctx.api_client("")?;
+
+        let measurements = client
+            .disks()
+            .metrics_list_all(
+                &self.disk,
+                Some(end),
+                self.metric.clone(),
+                &self.organization,
+                &self.project,
+                Some(start),
+            )
+            .await?;
+
+        let mut samples = Vec::new();
+        let mut cumulative = false;
+        for measurement in measurements {
+            let (value, is_cumulative) = datum_value(&measurement.datum)?;
+            cumulative |= is_cumulative;
+            samples.push(DiskMetricSample {
+                timestamp: measurement.timestamp,
+                value,
+            });
+        }
+
+        let style = match &self.chart {
+            Some(style) => style,
+            None => {
+                let format = ctx.format(&self.format)?;
+                ctx.io.write_output_for_vec(&format, &samples)?;
+                return Ok(());
+            }
+        };
+
+        // For counters, the interesting part is how much they moved between samples.
+        let samples = if cumulative { deltas(&samples) } else { samples };
+
+        if samples.is_empty() {
+            writeln!(
+                ctx.io.out,
+                "Not enough {} samples for disk {} between {} and {} to draw a chart",
+                self.metric, self.disk, start, end
+            )?;
+            return Ok(());
+        }
+
+        let cs = ctx.io.color_scheme();
+        let width = std::cmp::max(ctx.io.terminal_width(), 20) as usize;
+        writeln!(
+            ctx.io.out,
+            "{}",
+            cs.bold(&format!(
+                "{} of disk {}{}",
+                self.metric,
+                self.disk,
+                if cumulative { " (change per sample)" } else { "" }
+            ))
+        )?;
+        match style {
+            ChartStyle::Sparkline => {
+                let values: Vec<f64> = samples.iter().map(|s| s.value).collect();
+                writeln!(ctx.io.out, "{}", sparkline(&values, width))?;
+            }
+            ChartStyle::Bars => {
+                for line in bar_chart(&samples, width, 24) {
+                    writeln!(ctx.io.out, "{}", line)?;
+                }
+            }
+        }
+
+        let min = samples.iter().map(|s| s.value).fold(f64::INFINITY, f64::min);
+        let max = samples.iter().map(|s| s.value).fold(f64::NEG_INFINITY, f64::max);
+        writeln!(
+            ctx.io.out,
+            "{}",
+            cs.gray(&format!(
+                "{} to {}, {} samples, min {}, max {}",
+                samples[0].timestamp,
+                samples[samples.len() - 1].timestamp,
+                samples.len(),
+                min,
+                max
+            ))
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Returns the value of the datum as a number and whether it is a counter.
+fn datum_value(datum: &oxide_api::types::Datum) -> Result<(f64, bool)> {
+    // The datum is tagged with its type, the numeric ones hold either the value
+    // itself or, for counters, an object with the value and its start time.
+    let value = serde_json::to_value(datum)?;
+    let datum_type = value["type"].as_str().unwrap_or_default();
+
+    let number = match datum_type {
+        "bool" => value["datum"].as_bool().map(|b| if b { 1.0 } else { 0.0 }),
+        "i64" | "f64" => value["datum"].as_f64(),
+        "cumulative_i64" | "cumulative_f64" => value["datum"]["value"].as_f64(),
+        _ => None,
+    };
+
+    match number {
+        Some(n) => Ok((n, datum_type.starts_with("cumulative_"))),
+        None => Err(anyhow!("cannot show metric samples of type {}", datum_type)),
+    }
+}
+
+/// Returns the change between consecutive samples, at the time of the later one.
+fn deltas(samples: &[DiskMetricSample]) -> Vec<DiskMetricSample> {
+    samples
+        .windows(2)
+        .map(|w| DiskMetricSample {
+            timestamp: w[1].timestamp,
+            value: w[1].value - w[0].value,
+        })
+        .collect()
+}
+
+/// Splits the values into at most `n` consecutive buckets and returns the mean of each.
+fn bucket_means(values: &[f64], n: usize) -> Vec<f64> {
+    let n = std::cmp::min(n, values.len());
+
+    (0..n)
+        .map(|i| {
+            let bucket = &values[i * values.len() / n..(i + 1) * values.len() / n];
+            bucket.iter().sum::<f64>() / bucket.len() as f64
+        })
+        .collect()
+}
+
+const SPARK_TICKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Draws the values as a line of block characters at most `width` wide.
+fn sparkline(values: &[f64], width: usize) -> String {
+    let values = bucket_means(values, width);
+
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|v| {
+            if range == 0.0 {
+                SPARK_TICKS[0]
+            } else {
+                let i = ((v - min) / range * (SPARK_TICKS.len() - 1) as f64).round() as usize;
+                SPARK_TICKS[i]
+            }
+        })
+        .collect()
+}
+
+/// Draws the samples as at most `rows` horizontal bars, labelled with the time of
+/// the first sample in each, fitting in `width` columns.
+fn bar_chart(samples: &[DiskMetricSample], width: usize, rows: usize) -> Vec<String> {
+    let values: Vec<f64> = samples.iter().map(|s| s.value).collect();
+    let means = bucket_means(&values, rows);
+    let n = means.len();
+
+    let labels: Vec<String> = (0..n)
+        .map(|i| {
+            samples[i * samples.len() / n]
+                .timestamp
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .collect();
+    let numbers: Vec<String> = means.iter().map(|v| format!("{:.2}", v)).collect();
+
+    let label_width = labels.iter().map(|l| l.len()).max().unwrap_or_default();
+    let number_width = numbers.iter().map(|v| v.len()).max().unwrap_or_default();
+    // The label, a separator, a space before the number and the number itself.
+    let bar_width = width.saturating_sub(label_width + number_width + 4).max(1);
+
+    let max = means.iter().cloned().fold(0.0, f64::max);
+
+    (0..n)
+        .map(|i| {
+            let len = if max > 0.0 {
+                (means[i].max(0.0) / max * bar_width as f64).round() as usize
+            } else {
+                0
+            };
+            format!(
+                "{:<label_width$} │{:<bar_width$} {:>number_width$}",
+                labels[i],
+                "█".repeat(len),
+                numbers[i],
+                label_width = label_width,
+                bar_width = bar_width,
+                number_width = number_width
+            )
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
@@ -287,6 +763,60 @@ mod test {
                 want_out: "".to_string(),
                 want_err: "--confirm required when not running interactively".to_string(),
             },
+            TestItem {
+                name: "attach by name no organization".to_string(),
+                cmd: crate::cmd_disk::SubCommand::Attach(crate::cmd_disk::CmdDiskAttach {
+                    disk: "things".to_string(),
+                    instance: "stuff".to_string(),
+                    organization: "".to_string(),
+                    project: "foo".to_string(),
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "-o|--organization required when the disk is given by name".to_string(),
+            },
+            TestItem {
+                name: "edit no file or editor".to_string(),
+                cmd: crate::cmd_disk::SubCommand::Edit(crate::cmd_disk::CmdDiskEdit {
+                    disk: "things".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    from_file: "".to_string(),
+                    editor: false,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--from-file or --editor required".to_string(),
+            },
+            TestItem {
+                name: "edit by name no project".to_string(),
+                cmd: crate::cmd_disk::SubCommand::Edit(crate::cmd_disk::CmdDiskEdit {
+                    disk: "things".to_string(),
+                    organization: "foo".to_string(),
+                    project: "".to_string(),
+                    from_file: "-".to_string(),
+                    editor: false,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "-p|--project required when the disk is given by name".to_string(),
+            },
+            TestItem {
+                name: "delete by name no project".to_string(),
+                cmd: crate::cmd_disk::SubCommand::Delete(crate::cmd_disk::CmdDiskDelete {
+                    disk: "things".to_string(),
+                    organization: "bar".to_string(),
+                    project: "".to_string(),
+                    confirm: true,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "-p|--project required when the disk is given by name".to_string(),
+            },
             TestItem {
                 name: "list zero limit".to_string(),
                 cmd: crate::cmd_disk::SubCommand::List(crate::cmd_disk::CmdDiskList {
@@ -302,6 +832,23 @@ mod test {
                 want_out: "".to_string(),
                 want_err: "--limit must be greater than 0".to_string(),
             },
+            TestItem {
+                name: "metrics since after until".to_string(),
+                cmd: crate::cmd_disk::SubCommand::Metrics(crate::cmd_disk::CmdDiskMetrics {
+                    disk: "things".to_string(),
+                    metric: oxide_api::types::DiskMetricName::ReadBytes,
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    since: crate::types::TimeSpec::Ago(chrono::Duration::minutes(5)),
+                    until: crate::types::TimeSpec::Ago(chrono::Duration::hours(1)),
+                    chart: None,
+                    format: None,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--since must be before --until".to_string(),
+            },
         ];
 
         let mut config = crate::config::new_blank_config().unwrap();
@@ -344,4 +891,46 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_disk_metrics_charts() {
+        assert_eq!(crate::cmd_disk::bucket_means(&[1.0, 3.0, 5.0, 7.0], 2), vec![2.0, 6.0]);
+        assert_eq!(crate::cmd_disk::bucket_means(&[1.0, 2.0], 10), vec![1.0, 2.0]);
+
+        assert_eq!(
+            crate::cmd_disk::sparkline(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0], 80),
+            "▁▂▃▄▅▆▇█"
+        );
+        assert_eq!(crate::cmd_disk::sparkline(&[4.0, 4.0, 4.0], 80), "▁▁▁");
+        assert_eq!(crate::cmd_disk::sparkline(&[0.0, 7.0, 0.0, 7.0], 2), "▁▁");
+
+        let t = chrono::DateTime::parse_from_rfc3339("2022-08-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let samples = vec![
+            crate::cmd_disk::DiskMetricSample {
+                timestamp: t,
+                value: 10.0,
+            },
+            crate::cmd_disk::DiskMetricSample {
+                timestamp: t + chrono::Duration::seconds(10),
+                value: 30.0,
+            },
+            crate::cmd_disk::DiskMetricSample {
+                timestamp: t + chrono::Duration::seconds(20),
+                value: 35.0,
+            },
+        ];
+
+        let deltas = crate::cmd_disk::deltas(&samples);
+        assert_eq!(deltas.iter().map(|s| s.value).collect::<Vec<_>>(), vec![20.0, 5.0]);
+
+        assert_eq!(
+            crate::cmd_disk::bar_chart(&deltas, 40, 24),
+            vec![
+                "2022-08-01 12:00:10 │████████████ 20.00".to_string(),
+                "2022-08-01 12:00:20 │███           5.00".to_string(),
+            ]
+        );
+    }
 }
@@ -0,0 +1,406 @@
+use std::io::Write;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use parse_display::Display;
+
+/// Look up a resource by its ID.
+///
+/// Log lines and sagas often only refer to resources by ID. This finds the resource
+/// with the ID, whatever kind it is, and shows its kind and full path, like
+/// `organization/project/instance`, along with the resource itself.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdId {
+    /// The ID of the resource to look up.
+    #[clap(name = "id", required = true)]
+    pub id: String,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+/// The kinds of resources that can be looked up by ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, serde::Serialize)]
+#[display(style = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum ResourceKind {
+    Organization,
+    Project,
+    Instance,
+    NetworkInterface,
+    Disk,
+    Snapshot,
+    Image,
+    GlobalImage,
+    Vpc,
+    Subnet,
+    Router,
+    Route,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdId {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if uuid::Uuid::parse_str(&self.id).is_err() {
+            return Err(anyhow!("{} is not an ID", self.id));
+        }
+
+        let format = ctx.format(&self.format)?;
+
+        let client = ctx.api_client("")?;
+        let id = &self.id;
+
+        // IDs are unique across all kinds of resources, so we try each kind until one
+        // of them is found.
+        if let Some(r) = probe(client.instances().view_by_id(id)).await? {
+            let path = format!("{}/{}", project_path(&client, &r.project_id).await?, r.name);
+            return write_resource(ctx, &format, ResourceKind::Instance, &path, &r);
+        }
+
+        if let Some(r) = probe(client.disks().view_by_id(id)).await? {
+            let path = format!("{}/{}", project_path(&client, &r.project_id).await?, r.name);
+            return write_resource(ctx, &format, ResourceKind::Disk, &path, &r);
+        }
+
+        if let Some(r) = probe(client.projects().view_by_id(id)).await? {
+            let path = project_path(&client, &r.id).await?;
+            return write_resource(ctx, &format, ResourceKind::Project, &path, &r);
+        }
+
+        if let Some(r) = probe(client.organizations().view_by_id(id)).await? {
+            let path = r.name.to_string();
+            return write_resource(ctx, &format, ResourceKind::Organization, &path, &r);
+        }
+
+        if let Some(r) = probe(client.instances().network_interface_view_by_id(id)).await? {
+            let instance = client.instances().view_by_id(&r.instance_id).await?;
+            let path = format!(
+                "{}/{}/{}",
+                project_path(&client, &instance.project_id).await?,
+                instance.name,
+                r.name
+            );
+            return write_resource(ctx, &format, ResourceKind::NetworkInterface, &path, &r);
+        }
+
+        if let Some(r) = probe(client.snapshots().view_by_id(id)).await? {
+            let path = format!("{}/{}", project_path(&client, &r.project_id).await?, r.name);
+            return write_resource(ctx, &format, ResourceKind::Snapshot, &path, &r);
+        }
+
+        if let Some(r) = probe(client.images().view_by_id(id)).await? {
+            let path = format!("{}/{}", project_path(&client, &r.project_id).await?, r.name);
+            return write_resource(ctx, &format, ResourceKind::Image, &path, &r);
+        }
+
+        if let Some(r) = probe(client.images_global().view_by_id(id)).await? {
+            let path = r.name.to_string();
+            return write_resource(ctx, &format, ResourceKind::GlobalImage, &path, &r);
+        }
+
+        if let Some(r) = probe(client.vpcs().view_by_id(id)).await? {
+            let path = vpc_path(&client, &r.id).await?;
+            return write_resource(ctx, &format, ResourceKind::Vpc, &path, &r);
+        }
+
+        if let Some(r) = probe(client.vpcs().subnet_view_by_id(id)).await? {
+            let path = format!("{}/{}", vpc_path(&client, &r.vpc_id).await?, r.name);
+            return write_resource(ctx, &format, ResourceKind::Subnet, &path, &r);
+        }
+
+        if let Some(r) = probe(client.vpcs().router_view_by_id(id)).await? {
+            let path = format!("{}/{}", vpc_path(&client, &r.vpc_id).await?, r.name);
+            return write_resource(ctx, &format, ResourceKind::Router, &path, &r);
+        }
+
+        if let Some(r) = probe(client.vpcs().router_route_view_by_id(id)).await? {
+            let router = client.vpcs().router_view_by_id(&r.vpc_router_id).await?;
+            let path = format!(
+                "{}/{}/{}",
+                vpc_path(&client, &router.vpc_id).await?,
+                router.name,
+                r.name
+            );
+            return write_resource(ctx, &format, ResourceKind::Route, &path, &r);
+        }
+
+        Err(anyhow!("no resource found with ID {}", id))
+    }
+}
+
+/// Write the resource along with its kind and path.
+fn write_resource<T: serde::Serialize + tabled::Tabled>(
+    ctx: &mut crate::context::Context,
+    format: &crate::types::FormatOutput,
+    kind: ResourceKind,
+    path: &str,
+    resource: &T,
+) -> Result<()> {
+    match format {
+        crate::types::FormatOutput::Table => {
+            let cs = ctx.io.color_scheme();
+            writeln!(ctx.io.out, "{} {}", cs.bold(&kind.to_string()), path)?;
+            ctx.io.write_output(format, resource)?;
+        }
+        crate::types::FormatOutput::Json => {
+            ctx.io.write_output_json(&serde_json::json!({
+                "kind": kind,
+                "path": path,
+                "resource": resource,
+            }))?;
+        }
+        crate::types::FormatOutput::Yaml => {
+            ctx.io.write_output_yaml(&serde_json::json!({
+                "kind": kind,
+                "path": path,
+                "resource": resource,
+            }))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Await a lookup by ID, returning `None` if there is nothing with the ID.
+pub async fn probe<T, E>(lookup: impl std::future::Future<Output = Result<T, E>>) -> Result<Option<T>>
+where
+    E: Into<anyhow::Error>,
+{
+    match lookup.await {
+        Ok(resource) => Ok(Some(resource)),
+        Err(err) => {
+            let err: anyhow::Error = err.into();
+            if is_not_found(&err) {
+                Ok(None)
+            } else {
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Returns if the error is the server saying the resource does not exist.
+fn is_not_found(err: &anyhow::Error) -> bool {
+    crate::cmd::api_error_status(err) == Some(http::StatusCode::NOT_FOUND)
+}
+
+/// The names of a resource and the parents it is reached through, as commands that take
+/// names expect them. Parents the resource doesn't have are left empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourcePath {
+    pub organization: String,
+    pub project: String,
+    pub vpc: String,
+    pub router: String,
+    pub instance: String,
+    pub name: String,
+}
+
+/// Resolve the ID of a resource of the kind to the names of it and its parents, so it
+/// can be passed to API calls that only take names.
+pub async fn resolve_path(client: &oxide_api::Client, kind: ResourceKind, id: &str) -> Result<ResourcePath> {
+    let mut path = ResourcePath::default();
+
+    let project_id = match kind {
+        ResourceKind::Organization => {
+            path.name = client.organizations().view_by_id(id).await?.name;
+            return Ok(path);
+        }
+        ResourceKind::GlobalImage => {
+            path.name = client.images_global().view_by_id(id).await?.name;
+            return Ok(path);
+        }
+        ResourceKind::Project => id.to_string(),
+        ResourceKind::Instance => {
+            let r = client.instances().view_by_id(id).await?;
+            path.name = r.name;
+            r.project_id
+        }
+        ResourceKind::NetworkInterface => {
+            let r = client.instances().network_interface_view_by_id(id).await?;
+            let instance = client.instances().view_by_id(&r.instance_id).await?;
+            path.name = r.name;
+            path.instance = instance.name;
+            instance.project_id
+        }
+        ResourceKind::Disk => {
+            let r = client.disks().view_by_id(id).await?;
+            path.name = r.name;
+            r.project_id
+        }
+        ResourceKind::Snapshot => {
+            let r = client.snapshots().view_by_id(id).await?;
+            path.name = r.name;
+            r.project_id
+        }
+        ResourceKind::Image => {
+            let r = client.images().view_by_id(id).await?;
+            path.name = r.name;
+            r.project_id
+        }
+        ResourceKind::Vpc => {
+            let r = client.vpcs().view_by_id(id).await?;
+            path.name = r.name;
+            r.project_id
+        }
+        ResourceKind::Subnet => {
+            let r = client.vpcs().subnet_view_by_id(id).await?;
+            let vpc = client.vpcs().view_by_id(&r.vpc_id).await?;
+            path.name = r.name;
+            path.vpc = vpc.name;
+            vpc.project_id
+        }
+        ResourceKind::Router => {
+            let r = client.vpcs().router_view_by_id(id).await?;
+            let vpc = client.vpcs().view_by_id(&r.vpc_id).await?;
+            path.name = r.name;
+            path.vpc = vpc.name;
+            vpc.project_id
+        }
+        ResourceKind::Route => {
+            let r = client.vpcs().router_route_view_by_id(id).await?;
+            let router = client.vpcs().router_view_by_id(&r.vpc_router_id).await?;
+            let vpc = client.vpcs().view_by_id(&router.vpc_id).await?;
+            path.name = r.name;
+            path.router = router.name;
+            path.vpc = vpc.name;
+            vpc.project_id
+        }
+    };
+
+    let project = client.projects().view_by_id(&project_id).await?;
+    let organization = client.organizations().view_by_id(&project.organization_id).await?;
+    path.organization = organization.name;
+    if kind == ResourceKind::Project {
+        path.name = project.name;
+    } else {
+        path.project = project.name;
+    }
+
+    Ok(path)
+}
+
+/// Resolve a resource given by ID to the names of it and its parents, or return `None`
+/// if it was given by name.
+pub async fn resolve_if_id(
+    ctx: &mut crate::context::Context,
+    kind: ResourceKind,
+    name_or_id: &str,
+) -> Result<Option<ResourcePath>> {
+    if uuid::Uuid::parse_str(name_or_id).is_err() {
+        return Ok(None);
+    }
+
+    let client = ctx.api_client("")?;
+    Ok(Some(resolve_path(&client, kind, name_or_id).await?))
+}
+
+/// A command that acts on a resource given by name or ID, in a project given by the
+/// names of it and its organization.
+pub trait ByNameOrId: Clone {
+    /// The kind of resource the command acts on.
+    const KIND: ResourceKind;
+
+    /// Returns the fields holding the name or ID of the resource, and the names of the
+    /// organization and project that hold it.
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String);
+}
+
+/// If the command was given its resource by ID, returns a copy of it with the resource
+/// and its parents given by name instead, to run in its place. Otherwise checks the
+/// organization and project are given, and returns `None`.
+pub async fn by_name<C: ByNameOrId>(ctx: &mut crate::context::Context<'_>, cmd: &C) -> Result<Option<C>> {
+    let mut named = cmd.clone();
+    let (name, organization, project) = named.names_mut();
+
+    match resolve_if_id(ctx, C::KIND, name).await? {
+        Some(path) => {
+            *name = path.name;
+            *organization = path.organization;
+            *project = path.project;
+            Ok(Some(named))
+        }
+        None => {
+            require_project(C::KIND, organization, project)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Returns an error if the organization or project that hold a resource given by name
+/// are missing.
+pub fn require_project(kind: ResourceKind, organization: &str, project: &str) -> Result<()> {
+    if organization.is_empty() {
+        return Err(anyhow!("-o|--organization required when the {} is given by name", kind));
+    }
+
+    if project.is_empty() {
+        return Err(anyhow!("-p|--project required when the {} is given by name", kind));
+    }
+
+    Ok(())
+}
+
+/// Returns the `organization/project` path of the project with the ID.
+pub async fn project_path(client: &oxide_api::Client, project_id: &str) -> Result<String> {
+    let project = client.projects().view_by_id(project_id).await?;
+    let organization = client.organizations().view_by_id(&project.organization_id).await?;
+
+    Ok(format!("{}/{}", organization.name, project.name))
+}
+
+/// Returns the `organization/project/vpc` path of the VPC with the ID.
+pub async fn vpc_path(client: &oxide_api::Client, vpc_id: &str) -> Result<String> {
+    let vpc = client.vpcs().view_by_id(vpc_id).await?;
+
+    Ok(format!("{}/{}", project_path(client, &vpc.project_id).await?, vpc.name))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::cmd::Command;
+
+    #[test]
+    fn test_is_not_found() {
+        let err = anyhow::Error::new(oxide_api::types::Error::ObjectNotFound {
+            message: "not found: instance with id \"5b8a4041-0404-4e24-9404-1f0e2c5d8a7e\"".to_string(),
+        });
+        assert!(super::is_not_found(&err));
+
+        let err = anyhow::Error::new(oxide_api::types::Error::InternalError {
+            internal_message: "read 404 bytes from 5b8a4041-0404-4e24-9404-1f0e2c5d8a7e".to_string(),
+        });
+        assert!(!super::is_not_found(&err));
+
+        assert!(!super::is_not_found(&anyhow::anyhow!("not found: 404")));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_cmd_id_invalid() {
+        let mut config = crate::config::new_blank_config().unwrap();
+        let mut c = crate::config_from_env::EnvConfig::inherit_env(&mut config);
+
+        let (mut io, stdout_path, stderr_path) = crate::iostreams::IoStreams::test();
+        io.set_color_enabled(false);
+        io.set_never_prompt(true);
+        let mut ctx = crate::context::Context {
+            config: &mut c,
+            io,
+            debug: false,
+        };
+
+        let cmd_id = crate::cmd_id::CmdId {
+            id: "my-instance".to_string(),
+            format: None,
+        };
+
+        let err = cmd_id.run(&mut ctx).await.unwrap_err();
+        assert_eq!(err.to_string(), "my-instance is not an ID");
+        assert!(std::fs::read_to_string(stdout_path).unwrap().is_empty());
+        assert!(std::fs::read_to_string(stderr_path).unwrap().is_empty());
+    }
+}
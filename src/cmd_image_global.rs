@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::Parser;
 use cli_macro::crud_gen;
 
@@ -14,7 +14,9 @@ pub struct CmdImageGlobal {
     tag = "images:global",
 }]
 #[derive(Parser, Debug, Clone)]
-enum SubCommand {}
+enum SubCommand {
+    Create(CmdImageCreate),
+}
 
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdImageGlobal {
@@ -27,3 +29,125 @@ impl crate::cmd::Command for CmdImageGlobal {
         }
     }
 }
+
+/// Create a new global image.
+///
+/// Global images are available to all projects.
+///
+/// To create a global image interactively, use `oxide image global create` with no
+/// arguments.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdImageCreate {
+    /// The name of the image to create.
+    #[clap(name = "image", required = true)]
+    pub image: String,
+
+    /// The description for the image.
+    #[clap(long = "description", short = 'D', default_value_t)]
+    pub description: String,
+
+    /// The source of the image's contents, a url or snapshot id.
+    #[clap(long = "source", short = 's')]
+    pub source: Option<oxide_api::types::ImageSource>,
+
+    /// The name of the OS distribution of the image, like "alpine" or "ubuntu".
+    #[clap(long = "distribution", default_value_t)]
+    pub distribution: String,
+
+    /// The version of the OS distribution of the image, like "3.10" or "18.04".
+    #[clap(long = "version", default_value_t)]
+    pub version: String,
+
+    /// The block size of the image in bytes.
+    #[clap(long = "block-size", short = 'b', default_value_t)]
+    pub block_size: oxide_api::types::BlockSize,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdImageCreate {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let mut block_size = self.block_size.clone();
+        let mut description = self.description.clone();
+        let mut distribution = self.distribution.clone();
+        let mut image = self.image.clone();
+        let mut source = self.source.clone();
+        let mut version = self.version.clone();
+
+        if !ctx.io.can_prompt() {
+            if block_size.is_zero() {
+                return Err(anyhow!("-b|--block-size required in non-interactive mode"));
+            }
+            if description.is_empty() {
+                return Err(anyhow!("-D|--description required in non-interactive mode"));
+            }
+            if distribution.is_empty() {
+                return Err(anyhow!("--distribution required in non-interactive mode"));
+            }
+            if image.is_empty() {
+                return Err(anyhow!("[image] required in non-interactive mode"));
+            }
+            if source.is_none() {
+                return Err(anyhow!("-s|--source required in non-interactive mode"));
+            }
+            if version.is_empty() {
+                return Err(anyhow!("--version required in non-interactive mode"));
+            }
+        }
+
+        if image.is_empty() {
+            image = prompt_input("image name:")?;
+        }
+        if block_size.is_zero() {
+            block_size = prompt_input("image block_size")?;
+        }
+        if description.is_empty() {
+            description = prompt_input("image description")?;
+        }
+        if distribution.is_empty() {
+            distribution = prompt_input("image distribution")?;
+        }
+        if version.is_empty() {
+            version = prompt_input("image distribution version")?;
+        }
+        if source.is_none() {
+            use crate::prompt_ext::PromptExt;
+            source = Some(oxide_api::types::ImageSource::prompt(
+                "Input a url or snapshot id for the image source",
+            )?);
+        }
+
+        let client = ctx.api_client("")?;
+
+        client
+            .images_global()
+            .post(&oxide_api::types::GlobalImageCreate {
+                block_size,
+                description,
+                distribution: oxide_api::types::Distribution {
+                    name: distribution,
+                    version,
+                },
+                name: image.to_string(),
+                source: source.unwrap(),
+            })
+            .await?;
+
+        let cs = ctx.io.color_scheme();
+        writeln!(ctx.io.out, "{} Created global image {}", cs.success_icon(), image)?;
+
+        Ok(())
+    }
+}
+
+/// Prompt for a value of a field that wasn't given.
+fn prompt_input<T>(prompt: &str) -> Result<T>
+where
+    T: Clone + std::fmt::Display + std::str::FromStr,
+    T::Err: std::fmt::Display + std::fmt::Debug,
+{
+    dialoguer::Input::<T>::new()
+        .with_prompt(prompt)
+        .interact_text()
+        .map_err(|err| anyhow!("prompt failed: {}", err))
+}
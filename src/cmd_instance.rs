@@ -6,7 +6,8 @@ use cli_macro::crud_gen;
 
 /// Create, list, edit, view, and delete instances.
 ///
-/// Additionally, start, stop, and reboot instances.
+/// Additionally, start, stop, reboot, and migrate instances, and manage their
+/// network interfaces.
 #[derive(Parser, Debug, Clone)]
 #[clap(verbatim_doc_comment)]
 pub struct CmdInstance {
@@ -19,24 +20,37 @@ pub struct CmdInstance {
 }]
 #[derive(Parser, Debug, Clone)]
 enum SubCommand {
+    Cp(CmdInstanceCp),
     Disks(CmdInstanceDisks),
     Edit(CmdInstanceEdit),
+    Exec(CmdInstanceExec),
+    ExternalIps(CmdInstanceExternalIps),
+    Migrate(CmdInstanceMigrate),
+    #[clap(alias = "nics")]
+    Nic(crate::cmd_instance_nic::CmdInstanceNic),
     Ssh(CmdInstanceSsh),
     Start(CmdInstanceStart),
     Stop(CmdInstanceStop),
     Reboot(CmdInstanceReboot),
     Serial(CmdInstanceSerial),
+    #[clap(alias = "get")]
+    View(CmdInstanceView),
 }
 
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdInstance {
     async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
         match &self.subcmd {
+            SubCommand::Cp(cmd) => cmd.run(ctx).await,
             SubCommand::Create(cmd) => cmd.run(ctx).await,
             SubCommand::Delete(cmd) => cmd.run(ctx).await,
             SubCommand::Disks(cmd) => cmd.run(ctx).await,
             SubCommand::Edit(cmd) => cmd.run(ctx).await,
+            SubCommand::Exec(cmd) => cmd.run(ctx).await,
+            SubCommand::ExternalIps(cmd) => cmd.run(ctx).await,
             SubCommand::List(cmd) => cmd.run(ctx).await,
+            SubCommand::Migrate(cmd) => cmd.run(ctx).await,
+            SubCommand::Nic(cmd) => cmd.run(ctx).await,
             SubCommand::Serial(cmd) => cmd.run(ctx).await,
             SubCommand::Ssh(cmd) => cmd.run(ctx).await,
             SubCommand::Start(cmd) => cmd.run(ctx).await,
@@ -51,16 +65,16 @@ impl crate::cmd::Command for CmdInstance {
 #[derive(Parser, Debug, Clone)]
 #[clap(verbatim_doc_comment)]
 pub struct CmdInstanceDisks {
-    /// The instance to view the disks for.
+    /// The instance to view the disks for. Can be an ID or name.
     #[clap(name = "instance", required = true)]
     pub instance: String,
 
     /// The project that holds the instance.
-    #[clap(long, short, required = true)]
+    #[clap(long, short, default_value_t)]
     pub project: String,
 
     /// The organization to view the project.
-    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
     pub organization: String,
 
     #[doc = r" Output format."]
@@ -68,9 +82,21 @@ pub struct CmdInstanceDisks {
     pub format: Option<crate::types::FormatOutput>,
 }
 
+impl crate::cmd_id::ByNameOrId for CmdInstanceDisks {
+    const KIND: crate::cmd_id::ResourceKind = crate::cmd_id::ResourceKind::Instance;
+
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String) {
+        (&mut self.instance, &mut self.organization, &mut self.project)
+    }
+}
+
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdInstanceDisks {
     async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if let Some(cmd) = crate::cmd_id::by_name(ctx, self).await? {
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
         let client = ctx.api_client("")?;
 
         let results = client
@@ -89,16 +115,273 @@ impl crate::cmd::Command for CmdInstanceDisks {
     }
 }
 
+/// List the external IPs of an instance.
+///
+/// These are the addresses the instance can be reached at from outside of its VPC.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdInstanceExternalIps {
+    /// The instance to view the external IPs for. Can be an ID or name.
+    #[clap(name = "instance", required = true)]
+    pub instance: String,
+
+    /// The project that holds the instance.
+    #[clap(long, short, default_value_t)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+impl crate::cmd_id::ByNameOrId for CmdInstanceExternalIps {
+    const KIND: crate::cmd_id::ResourceKind = crate::cmd_id::ResourceKind::Instance;
+
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String) {
+        (&mut self.instance, &mut self.organization, &mut self.project)
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdInstanceExternalIps {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if let Some(cmd) = crate::cmd_id::by_name(ctx, self).await? {
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
+        let client = ctx.api_client("")?;
+
+        let results = client
+            .instances()
+            .external_ip_list(&self.instance, &self.organization, &self.project)
+            .await?;
+
+        let format = ctx.format(&self.format)?;
+        ctx.io.write_output_for_vec(&format, &results.items)?;
+        Ok(())
+    }
+}
+
+/// View instance.
+///
+/// Display information about an Oxide instance.
+///
+/// With `--web`, open the instance in a web browser instead.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdInstanceView {
+    /// The instance to view. Can be an ID or name.
+    #[clap(name = "instance", required = true)]
+    pub instance: String,
+
+    /// The project that holds the instance.
+    #[clap(long, short, default_value_t)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// Open the instance in the browser.
+    #[clap(short, long)]
+    pub web: bool,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+impl crate::cmd_id::ByNameOrId for CmdInstanceView {
+    const KIND: crate::cmd_id::ResourceKind = crate::cmd_id::ResourceKind::Instance;
+
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String) {
+        (&mut self.instance, &mut self.organization, &mut self.project)
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdInstanceView {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if self.web {
+            let url = format!("https://{}/{}", ctx.config.default_host()?, self.instance);
+
+            ctx.browser("", &url)?;
+            return Ok(());
+        }
+
+        if let Some(cmd) = crate::cmd_id::by_name(ctx, self).await? {
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
+        let client = ctx.api_client("")?;
+
+        let result = client
+            .instances()
+            .get(&self.instance, &self.organization, &self.project)
+            .await?;
+
+        let format = ctx.format(&self.format)?;
+        if format != crate::types::FormatOutput::Table {
+            ctx.io.write_output(&format, &result)?;
+            return Ok(());
+        }
+
+        // Instances are reached through their external IPs, which are not part of the
+        // instance itself, so we add them to the table.
+        let external_ips = client
+            .instances()
+            .external_ip_list(&self.instance, &self.organization, &self.project)
+            .await?;
+
+        ctx.io.write_output_table(&InstanceWithExternalIps {
+            instance: result,
+            external_ips: external_ips.items,
+        })?;
+        Ok(())
+    }
+}
+
+/// An instance along with its external IPs, for the table output of `instance view`.
+struct InstanceWithExternalIps {
+    instance: oxide_api::types::Instance,
+    external_ips: Vec<oxide_api::types::ExternalIp>,
+}
+
+impl tabled::Tabled for InstanceWithExternalIps {
+    fn fields(&self) -> Vec<String> {
+        let mut fields = tabled::Tabled::fields(&self.instance);
+        fields.push(format_external_ips(&self.external_ips));
+        fields
+    }
+
+    fn headers() -> Vec<String> {
+        let mut headers = <oxide_api::types::Instance as tabled::Tabled>::headers();
+        headers.push("external_ips".to_string());
+        headers
+    }
+}
+
+/// Formats external IPs as a comma separated list of addresses.
+fn format_external_ips(external_ips: &[oxide_api::types::ExternalIp]) -> String {
+    external_ips
+        .iter()
+        .map(|ip| ip.ip.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Edit instance settings.
+///
+/// With `--editor`, the instance is opened as YAML in your editor and the fields you
+/// change are applied once you save and exit. With `--from-file`, the edited instance
+/// is read from a YAML or JSON file instead, or from standard input if the file is `-`.
+/// Fields left out are left as they are.
+///
+/// The API only allows changing the `run_state` of an instance, to `running` or
+/// `stopped`, which starts or stops it. Edits to any other field, including `name`
+/// and `description`, are refused before anything is changed.
+///
+///     # stop an instance
+///     $ echo 'run_state: stopped' | oxide instance edit things -F - -o foo -p bar
 #[derive(Parser, Debug, Clone)]
 #[clap(verbatim_doc_comment)]
-pub struct CmdInstanceEdit {}
+pub struct CmdInstanceEdit {
+    /// The instance to edit. Can be an ID or name.
+    #[clap(name = "instance", required = true)]
+    instance: String,
+
+    /// The project that holds the instance.
+    #[clap(long, short, default_value_t)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// Read the edited instance from a YAML or JSON file, or `-` for standard input.
+    #[clap(long, short = 'F', default_value_t, conflicts_with = "editor")]
+    pub from_file: String,
+
+    /// Edit the instance in the configured editor.
+    #[clap(long)]
+    pub editor: bool,
+}
+
+impl crate::cmd_id::ByNameOrId for CmdInstanceEdit {
+    const KIND: crate::cmd_id::ResourceKind = crate::cmd_id::ResourceKind::Instance;
+
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String) {
+        (&mut self.instance, &mut self.organization, &mut self.project)
+    }
+}
 
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdInstanceEdit {
-    async fn run(&self, _ctx: &mut crate::context::Context) -> Result<()> {
-        println!("Not implemented yet in omicron.");
-        Ok(())
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if self.from_file.is_empty() && !self.editor {
+            return Err(anyhow!("--from-file or --editor required"));
+        }
+
+        if self.editor && !ctx.io.can_prompt() {
+            return Err(anyhow!("--editor requires a terminal, use --from-file instead"));
+        }
+
+        if let Some(cmd) = crate::cmd_id::by_name(ctx, self).await? {
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
+        let client = ctx.api_client("")?;
+
+        let instance = client
+            .instances()
+            .get(&self.instance, &self.organization, &self.project)
+            .await?;
+
+        let current = serde_json::to_value(&instance)?;
+        let edited = crate::edit::read_edited(ctx, &instance, &self.from_file)?;
+        let changed = crate::edit::changed_fields(&current, &edited)?;
+        if changed.is_empty() {
+            let cs = ctx.io.color_scheme();
+            writeln!(
+                ctx.io.out,
+                "{} No changes to instance {}",
+                cs.warning_icon(),
+                self.instance
+            )?;
+            return Ok(());
+        }
+
+        crate::edit::refuse_uneditable("instance", &changed, &["run_state"])?;
+
+        let run_state = edited.get("run_state").ok_or_else(|| anyhow!("run_state is missing"))?;
+        match run_state.as_str() {
+            Some("running") => {
+                let cmd = CmdInstanceStart {
+                    instance: self.instance.to_string(),
+                    organization: self.organization.to_string(),
+                    project: self.project.to_string(),
+                };
+                crate::cmd::Command::run(&cmd, ctx).await
+            }
+            Some("stopped") => {
+                let cmd = CmdInstanceStop {
+                    instance: self.instance.to_string(),
+                    organization: self.organization.to_string(),
+                    project: self.project.to_string(),
+                    // Editing the run state to stopped is confirmation enough.
+                    confirm: true,
+                };
+                crate::cmd::Command::run(&cmd, ctx).await
+            }
+            _ => Err(anyhow!(
+                "run_state can only be edited to running or stopped, not {}",
+                run_state
+            )),
+        }
     }
 }
 
@@ -111,17 +394,29 @@ pub struct CmdInstanceStart {
     instance: String,
 
     /// The project that holds the instance.
-    #[clap(long, short, required = true)]
+    #[clap(long, short, default_value_t)]
     pub project: String,
 
     /// The organization that holds the project.
-    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
     pub organization: String,
 }
 
+impl crate::cmd_id::ByNameOrId for CmdInstanceStart {
+    const KIND: crate::cmd_id::ResourceKind = crate::cmd_id::ResourceKind::Instance;
+
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String) {
+        (&mut self.instance, &mut self.organization, &mut self.project)
+    }
+}
+
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdInstanceStart {
     async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if let Some(cmd) = crate::cmd_id::by_name(ctx, self).await? {
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
         let client = ctx.api_client("")?;
 
         let full_name = format!("{}/{}", self.organization, self.project);
@@ -165,11 +460,11 @@ pub struct CmdInstanceStop {
     instance: String,
 
     /// The project that holds the instance.
-    #[clap(long, short, required = true)]
+    #[clap(long, short, default_value_t)]
     pub project: String,
 
     /// The organization that holds the project.
-    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
     pub organization: String,
 
     /// Confirm stop without prompting.
@@ -177,6 +472,14 @@ pub struct CmdInstanceStop {
     pub confirm: bool,
 }
 
+impl crate::cmd_id::ByNameOrId for CmdInstanceStop {
+    const KIND: crate::cmd_id::ResourceKind = crate::cmd_id::ResourceKind::Instance;
+
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String) {
+        (&mut self.instance, &mut self.organization, &mut self.project)
+    }
+}
+
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdInstanceStop {
     async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
@@ -184,6 +487,10 @@ impl crate::cmd::Command for CmdInstanceStop {
             return Err(anyhow!("--confirm required when not running interactively"));
         }
 
+        if let Some(cmd) = crate::cmd_id::by_name(ctx, self).await? {
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
         let client = ctx.api_client("")?;
 
         let full_name = format!("{}/{}", self.organization, self.project);
@@ -245,11 +552,11 @@ pub struct CmdInstanceReboot {
     instance: String,
 
     /// The project that holds the instance.
-    #[clap(long, short, required = true)]
+    #[clap(long, short, default_value_t)]
     pub project: String,
 
     /// The organization that holds the project.
-    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
     pub organization: String,
 
     /// Confirm reboot without prompting.
@@ -257,6 +564,14 @@ pub struct CmdInstanceReboot {
     pub confirm: bool,
 }
 
+impl crate::cmd_id::ByNameOrId for CmdInstanceReboot {
+    const KIND: crate::cmd_id::ResourceKind = crate::cmd_id::ResourceKind::Instance;
+
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String) {
+        (&mut self.instance, &mut self.organization, &mut self.project)
+    }
+}
+
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdInstanceReboot {
     async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
@@ -264,6 +579,10 @@ impl crate::cmd::Command for CmdInstanceReboot {
             return Err(anyhow!("--confirm required when not running interactively"));
         }
 
+        if let Some(cmd) = crate::cmd_id::by_name(ctx, self).await? {
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
         let client = ctx.api_client("")?;
 
         let full_name = format!("{}/{}", self.organization, self.project);
@@ -316,6 +635,141 @@ impl crate::cmd::Command for CmdInstanceReboot {
     }
 }
 
+/// Migrate an instance to another sled.
+///
+/// The instance keeps running while it is moved. This waits until the migration has
+/// finished and the instance is running on the new sled. If `--to-sled` is not
+/// given, you will be prompted to select a sled.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdInstanceMigrate {
+    /// The instance to migrate. Can be an ID or name.
+    #[clap(name = "instance", required = true)]
+    instance: String,
+
+    /// The project that holds the instance.
+    #[clap(long, short, default_value_t)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// The ID of the sled to migrate the instance to.
+    #[clap(long = "to-sled", short = 's', default_value = "")]
+    pub to_sled: String,
+}
+
+impl crate::cmd_id::ByNameOrId for CmdInstanceMigrate {
+    const KIND: crate::cmd_id::ResourceKind = crate::cmd_id::ResourceKind::Instance;
+
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String) {
+        (&mut self.instance, &mut self.organization, &mut self.project)
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdInstanceMigrate {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if self.to_sled.is_empty() && !ctx.io.can_prompt() {
+            return Err(anyhow!("-s|--to-sled required in non-interactive mode"));
+        }
+
+        if !self.to_sled.is_empty() && uuid::Uuid::parse_str(&self.to_sled).is_err() {
+            return Err(anyhow!("sled must be an ID, got {}", self.to_sled));
+        }
+
+        if let Some(cmd) = crate::cmd_id::by_name(ctx, self).await? {
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
+        let client = ctx.api_client("")?;
+
+        let full_name = format!("{}/{}", self.organization, self.project);
+
+        let sleds = client
+            .sleds()
+            .get_all(oxide_api::types::IdSortMode::IdAscending)
+            .await?;
+
+        // If they didn't specify a sled, prompt for it.
+        let sled = if self.to_sled.is_empty() {
+            if sleds.is_empty() {
+                return Err(anyhow!("no sleds found"));
+            }
+
+            let items: Vec<String> = sleds
+                .iter()
+                .map(|s| format!("{} ({})", s.id, s.service_address))
+                .collect();
+
+            match dialoguer::Select::new()
+                .with_prompt("Select sled to migrate to:")
+                .items(&items)
+                .interact()
+            {
+                Ok(index) => &sleds[index],
+                Err(err) => {
+                    return Err(anyhow!("prompt failed: {}", err));
+                }
+            }
+        } else {
+            sleds
+                .iter()
+                .find(|s| s.id == self.to_sled)
+                .ok_or_else(|| anyhow!("sled {} not found", self.to_sled))?
+        };
+
+        // Remember when the run state last changed, so we can tell once the migration
+        // is over even if we never see the instance migrating.
+        let before = client
+            .instances()
+            .get(&self.instance, &self.organization, &self.project)
+            .await?;
+
+        // Migrate the instance.
+        client
+            .instances()
+            .migrate(
+                &self.instance,
+                &self.organization,
+                &self.project,
+                &oxide_api::types::InstanceMigrate {
+                    dst_sled_id: sled.id.to_string(),
+                },
+            )
+            .await?;
+
+        let instance_state = InstanceDetails {
+            host: "".to_string(),
+            instance: self.instance.to_string(),
+            organization: self.organization.to_string(),
+            project: self.project.to_string(),
+        };
+
+        // Wait for the instance to be running again after migrating.
+        instance_state
+            .wait_for_state_since(ctx, Some(&before), oxide_api::types::InstanceState::Running)
+            .await?;
+
+        let cs = ctx.io.color_scheme();
+        writeln!(
+            ctx.io.out,
+            "{} Migrated instance {} in {} to sled {} ({})",
+            cs.success_icon(),
+            self.instance,
+            full_name,
+            sled.id,
+            sled.service_address
+        )?;
+
+        Ok(())
+    }
+}
+
+/// How long to wait for an instance to reach a state before giving up.
+const INSTANCE_WAIT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(600);
+
 #[derive(Debug, Clone, PartialEq)]
 struct InstanceDetails {
     host: String,
@@ -329,6 +783,19 @@ impl InstanceDetails {
         &self,
         ctx: &mut crate::context::Context<'_>,
         status: oxide_api::types::InstanceState,
+    ) -> Result<()> {
+        self.wait_for_state_since(ctx, None, status).await
+    }
+
+    /// Waits for the instance to be in the given state. If `before` is set, the run
+    /// state must also have changed since the instance was in that state, this is for
+    /// transitions that end in the state the instance started in, like migrating a
+    /// running instance.
+    async fn wait_for_state_since(
+        &self,
+        ctx: &mut crate::context::Context<'_>,
+        before: Option<&oxide_api::types::Instance>,
+        status: oxide_api::types::InstanceState,
     ) -> Result<()> {
         // Start the progress bar.
         let handle = ctx
@@ -340,7 +807,7 @@ impl InstanceDetails {
 
         let client = ctx.api_client(&self.host)?;
 
-        // TODO: we should probably time out here eventually with an error.
+        let started = std::time::Instant::now();
         let mut last_state = None;
         loop {
             let instance = client
@@ -348,10 +815,28 @@ impl InstanceDetails {
                 .get(&self.instance, &self.organization, &self.project)
                 .await?;
 
-            if status == instance.run_state {
+            let changed = match before {
+                Some(before) => instance.time_run_state_updated != before.time_run_state_updated,
+                None => true,
+            };
+
+            if changed && status == instance.run_state {
                 break;
             }
 
+            if instance.run_state == oxide_api::types::InstanceState::Failed {
+                if let Some(handle) = handle {
+                    handle.text(format!("Instance status now `{}`", instance.run_state));
+                    handle.error();
+                }
+
+                return Err(anyhow!(
+                    "instance {} failed while waiting for status to be `{}`",
+                    self.instance,
+                    status
+                ));
+            }
+
             if last_state.as_ref() != Some(&instance.run_state) {
                 if let Some(handle) = &handle {
                     handle.text(format!(
@@ -362,6 +847,20 @@ impl InstanceDetails {
                 last_state = Some(instance.run_state);
             }
 
+            if started.elapsed() > INSTANCE_WAIT_TIMEOUT {
+                if let Some(handle) = handle {
+                    handle.text(format!("Instance status still `{}`", instance.run_state));
+                    handle.error();
+                }
+
+                return Err(anyhow!(
+                    "timed out after {}s waiting for instance {} status to be `{}`",
+                    INSTANCE_WAIT_TIMEOUT.as_secs(),
+                    self.instance,
+                    status
+                ));
+            }
+
             // Back off a bit.
             tokio::time::sleep(std::time::Duration::from_millis(100)).await;
         }
@@ -380,6 +879,11 @@ impl InstanceDetails {
 ///
 /// This command is a thin wrapper around the **ssh(1)** command that takes care of
 /// authentication and the translation of the instance name into an IP address.
+///
+/// A temporary ed25519 key is generated and added to your user account for the
+/// length of the session, and removed again once **ssh(1)** exits, even if it is
+/// interrupted. The instance is reached at its external IP, or with `--internal`
+/// at the IP of its primary network interface.
 #[derive(Parser, Debug, Clone)]
 #[clap(verbatim_doc_comment, trailing_var_arg = true)]
 pub struct CmdInstanceSsh {
@@ -393,17 +897,22 @@ pub struct CmdInstanceSsh {
     pub args: Vec<String>,
 
     /// The project that holds the instance.
-    #[clap(long, short, required = true)]
+    #[clap(long, short, default_value_t)]
     pub project: String,
 
     /// The organization that holds the project.
-    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
     pub organization: String,
 
     /// The ssh user. This defaults to `$USER` on the host the command is run on.
     #[clap(long, short, required = true, env = "USER")]
     pub user: String,
 
+    /// Connect to the IP of the instance's primary network interface instead of
+    /// its external IP.
+    #[clap(long)]
+    pub internal: bool,
+
     /// Additional flags to be passed to **ssh(1)**. It is recommended that flags
     /// be passed using an assignment operator and quotes.
     /// Example: `--ssh-flag "-L 80:localhost:80"`.
@@ -411,61 +920,655 @@ pub struct CmdInstanceSsh {
     pub ssh_flags: Vec<String>,
 }
 
-#[async_trait::async_trait]
+impl crate::cmd_id::ByNameOrId for CmdInstanceSsh {
+    const KIND: crate::cmd_id::ResourceKind = crate::cmd_id::ResourceKind::Instance;
+
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String) {
+        (&mut self.instance, &mut self.organization, &mut self.project)
+    }
+}
+
+#[async_trait::async_trait]
 impl crate::cmd::Command for CmdInstanceSsh {
     async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if let Some(cmd) = crate::cmd_id::by_name(ctx, self).await? {
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
+        let ssh_flags = ssh_flag_args(&self.ssh_flags)?;
+
         let client = ctx.api_client("")?;
 
+        let ip = instance_ip(
+            &client,
+            &self.instance,
+            &self.organization,
+            &self.project,
+            self.internal,
+        )
+        .await?;
+
+        // Listen for interrupts from here on, so that interrupting ssh doesn't also
+        // kill us before the temporary key is cleaned up.
+        let mut interrupted = tokio::spawn(tokio::signal::ctrl_c());
+
         // Generate a key to use for ssh-ing into the instance.
         // We default to ed25519 here, since its a nice thing.
-        writeln!(ctx.io.out, "Generating a temporary ssh key...")?;
-        /* let key = thrussh_keys::key::KeyPair::generate_ed25519().unwrap();
-                let pubkey = key.clone_public_key();
-
-                writeln!(
-                    ctx.io.out,
-                    "Temporary public key has fingerprint `{}`",
-                    pubkey.fingerprint()
-                )?;
-
-                writeln!(
-                    ctx.io.out,
-                    "Temporary bytes are `ssh-ed25519 {}`",
-                    pubkey.public_key_base64()
-                )?;
-
-                println!("ARGS: {:?}", self.args);
-
-                // TODO: Add our pubkey to our Oxide user's authorized_keys.
-                writeln!(ctx.io.out, "Adding temporary ssh key to your user account...")?;
-        */
-        // TODO: We need to get the instance IP address.
-        let _instance = client
-            .instances()
-            .get(&self.instance, &self.organization, &self.project)
-            .await?;
+        writeln!(ctx.io.err_out, "Generating a temporary ssh key...")?;
+        let key = TemporaryKey::create(
+            &client,
+            &format!(
+                "Temporary key for oxide instance ssh to {}/{}/{}",
+                self.organization, self.project, self.instance
+            ),
+        )
+        .await?;
+
+        let result = async {
+            let mut child = tokio::process::Command::new("ssh")
+                .arg("-i")
+                .arg(&key.private_key_file)
+                .args(["-o", "IdentitiesOnly=yes"])
+                .args(&ssh_flags)
+                .arg(format!("{}@{}", self.user, ip))
+                .args(&self.args)
+                .spawn()
+                .map_err(|err| anyhow!("running ssh failed: {}", err))?;
+
+            let status = tokio::select! {
+                status = child.wait() => status?,
+                _ = &mut interrupted => {
+                    let _ = child.kill().await;
+                    return Err(anyhow!("interrupted"));
+                }
+            };
+
+            if !status.success() {
+                return Err(anyhow!("ssh exited with {}", status));
+            }
+
+            Ok(())
+        }
+        .await;
 
-        // Wrap the ssh command in a shell.
-        std::process::Command::new("ssh")
-            //.arg(host)
-            .args(&self.args)
-            .stdout(std::process::Stdio::inherit())
-            .stdin(std::process::Stdio::inherit())
-            .stderr(std::process::Stdio::inherit())
-            .output()?;
-
-        // TODO: When we are done, we need to remove our key from our Oxide user's authorized keys.
-        // This makes it act as a temporary key.
+        // When we are done, remove the key from our Oxide user's keys. This makes it
+        // act as a temporary key.
         writeln!(
-            ctx.io.out,
+            ctx.io.err_out,
             "Cleaning up the temporary ssh key from your user account..."
         )?;
+        let cleanup = key.remove(&client).await;
+
+        result.and(cleanup)
+    }
+}
+
+/// Returns the IP to reach the instance at, its first external IP or, if `internal`
+/// is set, the IP of its primary network interface.
+async fn instance_ip(
+    client: &oxide_api::Client,
+    instance: &str,
+    organization: &str,
+    project: &str,
+    internal: bool,
+) -> Result<String> {
+    if internal {
+        let nics = client
+            .instances()
+            .network_interfaces_get_all(
+                instance,
+                organization,
+                project,
+                oxide_api::types::NameSortMode::NameAscending,
+            )
+            .await?;
+
+        return nics
+            .iter()
+            .find(|nic| nic.primary)
+            .or_else(|| nics.first())
+            .map(|nic| nic.ip.to_string())
+            .ok_or_else(|| anyhow!("instance {} has no network interfaces", instance));
+    }
+
+    let external_ips = client
+        .instances()
+        .external_ip_list(instance, organization, project)
+        .await?;
+
+    external_ips.items.first().map(|ip| ip.ip.to_string()).ok_or_else(|| {
+        anyhow!(
+            "instance {} has no external IP, use --internal to connect to its network interface",
+            instance
+        )
+    })
+}
+
+/// Split the `--ssh-flag` values into the arguments to pass to ssh.
+fn ssh_flag_args(ssh_flags: &[String]) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    for flag in ssh_flags {
+        match shlex::split(flag) {
+            Some(mut split) => args.append(&mut split),
+            None => return Err(anyhow!("invalid --ssh-flag: {}", flag)),
+        }
+    }
+
+    Ok(args)
+}
+
+/// An ssh key generated for a single session and added to the user's account.
+struct TemporaryKey {
+    /// The name the key is added to the account as.
+    name: String,
+    /// Where the private key is written to for ssh to use.
+    private_key_file: std::path::PathBuf,
+}
+
+impl TemporaryKey {
+    /// Generate an ed25519 key, write its private key to a temporary file, and add it
+    /// to the user's account.
+    async fn create(client: &oxide_api::Client, description: &str) -> Result<Self> {
+        let keypair = ssh_key::private::Ed25519Keypair::random(&mut ssh_key::rand_core::OsRng);
+        let private_key = ssh_key::PrivateKey::new(ssh_key::private::KeypairData::Ed25519(keypair), "oxide")?;
+
+        let name = format!("oxide-ssh-{}", uuid::Uuid::new_v4().to_simple());
+        let private_key_file = std::env::temp_dir().join(&name);
+        private_key.write_openssh_file(&private_key_file, ssh_key::LineEnding::default())?;
+
+        let params = oxide_api::types::SshKeyCreate {
+            name: name.to_string(),
+            description: description.to_string(),
+            public_key: private_key.public_key().to_string(),
+        };
+        if let Err(err) = client.sshkeys().post(&params).await {
+            let _ = std::fs::remove_file(&private_key_file);
+            return Err(err);
+        }
+
+        Ok(TemporaryKey { name, private_key_file })
+    }
+
+    /// Remove the key from the user's account and delete its private key file.
+    async fn remove(self, client: &oxide_api::Client) -> Result<()> {
+        let _ = std::fs::remove_file(&self.private_key_file);
+        client.sshkeys().delete_key(&self.name).await?;
+
+        Ok(())
+    }
+}
+
+/// Copy files to and from an instance.
+///
+/// One of the source and destination is a local path, and the other is a path on the
+/// instance, written as `<instance>:<path>`. The instance can be given by ID or name.
+///
+/// This command is a thin wrapper around the **scp(1)** command. The instance is
+/// reached at its external IP, or with `--internal` at the IP of its primary network
+/// interface, and authenticated with the SSH keys of your user account.
+///
+///     # copy a file to an instance
+///     $ oxide instance cp ./app.tar.gz my-instance:/tmp/ -o foo -p bar
+///
+///     # copy a directory from an instance
+///     $ oxide instance cp -r my-instance:/var/log ./logs -o foo -p bar
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdInstanceCp {
+    /// The path to copy from. Prefix it with `<instance>:` for a path on the instance.
+    #[clap(name = "source", required = true)]
+    pub source: String,
+
+    /// The path to copy to. Prefix it with `<instance>:` for a path on the instance.
+    #[clap(name = "destination", required = true)]
+    pub destination: String,
+
+    /// The project that holds the instance.
+    #[clap(long, short, default_value_t)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// The ssh user. This defaults to `$USER` on the host the command is run on.
+    #[clap(long, short, required = true, env = "USER")]
+    pub user: String,
+
+    /// Copy directories recursively.
+    #[clap(long, short)]
+    pub recursive: bool,
+
+    /// Connect to the IP of the instance's primary network interface instead of
+    /// its external IP.
+    #[clap(long)]
+    pub internal: bool,
+
+    /// Additional flags to be passed to **scp(1)**. It is recommended that flags
+    /// be passed using an assignment operator and quotes.
+    /// Example: `--ssh-flag "-o ProxyJump=bastion"`.
+    #[clap(long = "ssh-flag", multiple_occurrences = true, required = false)]
+    pub ssh_flags: Vec<String>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdInstanceCp {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let (instance, source, destination) = match (parse_remote(&self.source), parse_remote(&self.destination)) {
+            (Some(_), Some(_)) => return Err(anyhow!("copying between two instances is not supported")),
+            (None, None) => {
+                return Err(anyhow!(
+                    "one of the source and destination must be a path on an instance, like my-instance:/tmp"
+                ))
+            }
+            (Some((instance, path)), None) => (instance, CopyPath::Remote(path), CopyPath::Local(&self.destination)),
+            (None, Some((instance, path))) => (instance, CopyPath::Local(&self.source), CopyPath::Remote(path)),
+        };
+
+        if let Some(path) = crate::cmd_id::resolve_if_id(ctx, crate::cmd_id::ResourceKind::Instance, instance).await? {
+            let mut cmd = self.clone();
+            cmd.source = source.to_arg(&path.name);
+            cmd.destination = destination.to_arg(&path.name);
+            cmd.organization = path.organization;
+            cmd.project = path.project;
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
+        crate::cmd_id::require_project(crate::cmd_id::ResourceKind::Instance, &self.organization, &self.project)?;
+
+        let mut args = Vec::new();
+        if self.recursive {
+            args.push("-r".to_string());
+        }
+        args.append(&mut ssh_flag_args(&self.ssh_flags)?);
+
+        let client = ctx.api_client("")?;
+
+        let ip = instance_ip(&client, instance, &self.organization, &self.project, self.internal).await?;
+
+        // scp needs IPv6 addresses in brackets to tell them apart from the path.
+        let host = if ip.contains(':') {
+            format!("{}@[{}]", self.user, ip)
+        } else {
+            format!("{}@{}", self.user, ip)
+        };
+        args.push(source.to_arg(&host));
+        args.push(destination.to_arg(&host));
+
+        let status = subprocess::Exec::cmd("scp")
+            .args(&args)
+            .join()
+            .map_err(|err| anyhow!("running scp failed: {}", err))?;
+        if !status.success() {
+            return Err(anyhow!("scp exited with {:?}", status));
+        }
 
         Ok(())
     }
 }
 
+/// One side of a copy.
+enum CopyPath<'a> {
+    /// A path on the machine the command is run on.
+    Local(&'a str),
+    /// A path on the instance.
+    Remote(&'a str),
+}
+
+impl CopyPath<'_> {
+    /// Returns the path as an argument, prefixed with the host if it is on the instance.
+    fn to_arg(&self, host: &str) -> String {
+        match self {
+            CopyPath::Local(path) => path.to_string(),
+            CopyPath::Remote(path) => format!("{}:{}", host, path),
+        }
+    }
+}
+
+/// Split an `<instance>:<path>` argument into the instance and the path, or return
+/// `None` if the argument is a local path.
+///
+/// Anything with a slash before the first colon, like `./a:b`, is a local path.
+fn parse_remote(arg: &str) -> Option<(&str, &str)> {
+    let (instance, path) = arg.split_once(':')?;
+    if instance.is_empty() || instance.contains('/') || instance.contains('\\') {
+        return None;
+    }
+
+    Some((instance, path))
+}
+
+/// Run a command on many instances at once.
+///
+/// The command is run over **ssh(1)** on every instance of the project that matches
+/// `--selector`, at most `--parallel` at a time. Each line of output is prefixed with
+/// the name of the instance it came from, and a table of the exit codes is printed
+/// once all of them are done. If the command fails on any instance, this command exits
+/// with a non-zero status.
+///
+/// The selector is a comma separated list of `field=pattern` conditions on the fields
+/// of the instance, which all have to match. Patterns can use `*` and `?` wildcards.
+///
+/// Instances are authenticated with the SSH keys of your user account. Since ssh runs
+/// without a terminal, it cannot prompt to accept unknown host keys. Pass
+/// `--ssh-flag "-o StrictHostKeyChecking=accept-new"` to accept them.
+///
+///     # check the uptime of the web servers
+///     $ oxide instance exec -o foo -p bar --selector 'name=web-*' -- uptime
+///
+///     # restart a service on the running instances, two at a time
+///     $ oxide instance exec -o foo -p bar -s 'run_state=running' --parallel 2 -- sudo systemctl restart app
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment, trailing_var_arg = true)]
+pub struct CmdInstanceExec {
+    /// The command and args to run on the instances.
+    #[clap(name = "command", multiple_values = true, required = true)]
+    pub command: Vec<String>,
+
+    /// The project that holds the instances.
+    #[clap(long, short, required = true)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// The instances to run the command on, like `name=web-*`. All instances of the
+    /// project are selected if not specified.
+    #[clap(long, short, default_value_t)]
+    pub selector: String,
+
+    /// The most instances to run the command on at the same time.
+    #[clap(long, default_value = "10")]
+    pub parallel: usize,
+
+    /// The ssh user. This defaults to `$USER` on the host the command is run on.
+    #[clap(long, short, required = true, env = "USER")]
+    pub user: String,
+
+    /// Connect to the IPs of the instances' primary network interfaces instead of
+    /// their external IPs.
+    #[clap(long)]
+    pub internal: bool,
+
+    /// Additional flags to be passed to **ssh(1)**. It is recommended that flags
+    /// be passed using an assignment operator and quotes.
+    /// Example: `--ssh-flag "-o ConnectTimeout=5"`.
+    #[clap(long = "ssh-flag", multiple_occurrences = true, required = false)]
+    pub ssh_flags: Vec<String>,
+}
+
+/// The result of running the command on an instance.
+#[derive(Debug, Clone, serde::Serialize, tabled::Tabled)]
+pub struct ExecResult {
+    pub instance: String,
+    pub exit_code: String,
+    pub error: String,
+}
+
+/// What the tasks running the command report back.
+enum ExecEvent {
+    /// A line of output from the instance with the index.
+    Line { index: usize, line: String, stderr: bool },
+    /// The command finished on the instance with the index, with its exit code, or
+    /// an error if it could not be run.
+    Done { index: usize, result: Result<i32, String> },
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdInstanceExec {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if self.parallel < 1 {
+            return Err(anyhow!("--parallel must be greater than 0"));
+        }
+
+        let selector = parse_selector(&self.selector)?;
+        let ssh_flags = ssh_flag_args(&self.ssh_flags)?;
+
+        let client = ctx.api_client("")?;
+
+        let mut instances = Vec::new();
+        for instance in client
+            .instances()
+            .get_all(
+                &self.organization,
+                &self.project,
+                oxide_api::types::NameSortMode::NameAscending,
+            )
+            .await?
+        {
+            if selector_matches(&selector, &serde_json::to_value(&instance)?)? {
+                instances.push(instance.name);
+            }
+        }
+
+        if instances.is_empty() {
+            return Err(anyhow!(
+                "no instances in {}/{} match the selector",
+                self.organization,
+                self.project
+            ));
+        }
+
+        let mut results = instances
+            .iter()
+            .map(|name| ExecResult {
+                instance: name.to_string(),
+                exit_code: "-".to_string(),
+                error: String::new(),
+            })
+            .collect::<Vec<_>>();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(self.parallel));
+
+        // The addresses are looked up by the tasks, so that an instance doesn't wait on
+        // the lookups of the ones before it.
+        let client = std::sync::Arc::new(client);
+        for (index, name) in instances.iter().enumerate() {
+            let name = name.to_string();
+            let organization = self.organization.to_string();
+            let project = self.project.to_string();
+            let internal = self.internal;
+            let user = self.user.to_string();
+            let ssh_flags = ssh_flags.clone();
+            let command = self.command.clone();
+
+            let client = client.clone();
+            let tx = tx.clone();
+            let semaphore = semaphore.clone();
+            tokio::spawn(async move {
+                let result = match semaphore.acquire_owned().await {
+                    Ok(_permit) => match instance_ip(&client, &name, &organization, &project, internal).await {
+                        Ok(ip) => {
+                            let mut args = vec!["-o".to_string(), "BatchMode=yes".to_string()];
+                            args.extend(ssh_flags);
+                            args.push(format!("{}@{}", user, ip));
+                            args.extend(command);
+
+                            exec_ssh(index, args, &tx).await.map_err(|err| err.to_string())
+                        }
+                        Err(err) => Err(err.to_string()),
+                    },
+                    Err(err) => Err(err.to_string()),
+                };
+                let _ = tx.send(ExecEvent::Done { index, result });
+            });
+        }
+        drop(tx);
+
+        let cs = ctx.io.color_scheme();
+        let width = instances.iter().map(|name| name.len()).max().unwrap_or_default();
+        let prefixes = instances
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let prefix = format!("{:width$} |", name, width = width);
+                match index % 5 {
+                    0 => cs.cyan(&prefix),
+                    1 => cs.green(&prefix),
+                    2 => cs.yellow(&prefix),
+                    3 => cs.blue(&prefix),
+                    _ => cs.purple(&prefix),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        while let Some(event) = rx.recv().await {
+            match event {
+                ExecEvent::Line { index, line, stderr } => {
+                    if stderr {
+                        writeln!(ctx.io.err_out, "{} {}", prefixes[index], line)?;
+                    } else {
+                        writeln!(ctx.io.out, "{} {}", prefixes[index], line)?;
+                    }
+                }
+                ExecEvent::Done { index, result } => match result {
+                    Ok(code) => results[index].exit_code = code.to_string(),
+                    Err(err) => results[index].error = err,
+                },
+            }
+        }
+
+        writeln!(ctx.io.out)?;
+        ctx.io
+            .write_output_for_vec(&crate::types::FormatOutput::Table, &results)?;
+
+        let failed = results.iter().filter(|r| r.exit_code != "0").count();
+        if failed > 0 {
+            return Err(anyhow!(
+                "the command failed on {} of {} instances",
+                failed,
+                results.len()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Run ssh with the args, sending each line of its output as an event, and return its
+/// exit code.
+async fn exec_ssh(index: usize, args: Vec<String>, tx: &tokio::sync::mpsc::UnboundedSender<ExecEvent>) -> Result<i32> {
+    let mut child = tokio::process::Command::new("ssh")
+        .args(&args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| anyhow!("running ssh failed: {}", err))?;
+
+    let stdout = child.stdout.take().ok_or_else(|| anyhow!("ssh has no stdout"))?;
+    let stderr = child.stderr.take().ok_or_else(|| anyhow!("ssh has no stderr"))?;
+
+    let (status, _, _) = tokio::try_join!(
+        child.wait(),
+        forward_lines(index, stdout, false, tx),
+        forward_lines(index, stderr, true, tx),
+    )?;
+
+    // ssh exits with 255 when it fails itself, otherwise with the exit code of the
+    // command. Without a code, the command was killed by a signal.
+    status.code().ok_or_else(|| anyhow!("ssh was killed: {}", status))
+}
+
+/// Send each line read from the reader as an event.
+async fn forward_lines(
+    index: usize,
+    reader: impl tokio::io::AsyncRead + Unpin,
+    stderr: bool,
+    tx: &tokio::sync::mpsc::UnboundedSender<ExecEvent>,
+) -> std::io::Result<()> {
+    use tokio::io::AsyncBufReadExt;
+
+    let mut lines = tokio::io::BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        let _ = tx.send(ExecEvent::Line { index, line, stderr });
+    }
+
+    Ok(())
+}
+
+/// Parse a selector like `name=web-*,run_state=running` into its field and pattern
+/// pairs.
+fn parse_selector(selector: &str) -> Result<Vec<(String, String)>> {
+    selector
+        .split(',')
+        .map(|condition| condition.trim())
+        .filter(|condition| !condition.is_empty())
+        .map(|condition| match condition.split_once('=') {
+            Some((field, pattern)) if !field.trim().is_empty() => {
+                Ok((field.trim().to_string(), pattern.trim().to_string()))
+            }
+            _ => Err(anyhow!(
+                "invalid selector condition {}, expected field=pattern",
+                condition
+            )),
+        })
+        .collect()
+}
+
+/// Returns if the instance, as JSON, matches all the conditions of the selector.
+fn selector_matches(selector: &[(String, String)], instance: &serde_json::Value) -> Result<bool> {
+    for (field, pattern) in selector {
+        let value = match instance.get(field) {
+            Some(serde_json::Value::String(value)) => value.to_string(),
+            Some(value) => value.to_string(),
+            None => return Err(anyhow!("instances have no field {} to select by", field)),
+        };
+
+        if !glob_match(pattern, &value) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Returns if the value matches the pattern, where `*` matches any run of characters
+/// and `?` matches any single character.
+fn glob_match(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let value = value.chars().collect::<Vec<_>>();
+
+    let (mut p, mut v) = (0, 0);
+    // Where to go back to if what follows the last `*` fails to match.
+    let mut backtrack = None;
+    while v < value.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == value[v]) {
+            p += 1;
+            v += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, v));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // Let the `*` match one more character and try again.
+            p = star + 1;
+            v = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
 /// Read the buffered data from an instance's serial console.
+///
+/// With `--follow`, new output keeps being read until you press Ctrl-C. Failed reads
+/// are retried with a growing delay, so a brief outage doesn't end the session. With
+/// `--until`, reading stops as soon as new output matches the regex, which is handy
+/// for waiting on an instance to boot. The output already buffered is printed but not
+/// matched, unless `--byte-offset` is given:
+///
+///     # wait for the login prompt, logging the boot output to a file
+///     $ oxide instance serial my-instance -o foo -p bar --until 'login:' --output boot.log
+///
+/// With `--output`, the output is appended to a file rather than printed. Once the
+/// file reaches `--max-size` bytes, it is rotated to `<file>.1`, `<file>.1` to
+/// `<file>.2`, and so on, keeping at most `--max-files` rotated files.
 #[derive(Parser, Debug, Clone)]
 #[clap(verbatim_doc_comment, trailing_var_arg = true)]
 pub struct CmdInstanceSerial {
@@ -474,11 +1577,11 @@ pub struct CmdInstanceSerial {
     pub instance: String,
 
     /// The project that holds the instance.
-    #[clap(long, short, required = true)]
+    #[clap(long, short, default_value_t)]
     pub project: String,
 
     /// The organization that holds the project.
-    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    #[clap(long, short, default_value_t, env = "OXIDE_ORG")]
     pub organization: String,
 
     /// The maximum length of bytes to retrieve.
@@ -490,14 +1593,71 @@ pub struct CmdInstanceSerial {
     #[clap(long, short)]
     pub byte_offset: Option<i64>,
 
-    /// Whether to continuously read from the running instance's output.
+    /// Keep reading new output from the running instance until interrupted.
+    #[clap(long, short, alias = "continuous", short_alias = 'c')]
+    pub follow: bool,
+
+    /// Stop reading once new output matches this regex. Implies `--follow`.
+    #[clap(long)]
+    pub until: Option<String>,
+
+    /// Append the output to this file instead of printing it.
+    #[clap(long)]
+    pub output: Option<std::path::PathBuf>,
+
+    /// The size in bytes at which the `--output` file is rotated. 0 never rotates it.
+    #[clap(long, default_value = "10485760")]
+    pub max_size: u64,
+
+    /// The number of rotated `--output` files to keep.
+    #[clap(long, default_value = "5")]
+    pub max_files: usize,
+
+    /// Remove ANSI escape sequences, like colors, from the output.
+    #[clap(long)]
+    pub strip_ansi: bool,
+
+    /// Prefix each line with the time it was read.
     #[clap(long, short)]
-    pub continuous: bool,
+    pub timestamps: bool,
+}
+
+/// How many reads of the serial console in a row can fail before we give up following.
+const SERIAL_MAX_RETRIES: u32 = 10;
+
+/// How much of the most recent output is kept around to match `--until` against, so
+/// that matches split across reads are still found.
+const SERIAL_UNTIL_WINDOW: usize = 4096;
+
+impl crate::cmd_id::ByNameOrId for CmdInstanceSerial {
+    const KIND: crate::cmd_id::ResourceKind = crate::cmd_id::ResourceKind::Instance;
+
+    fn names_mut(&mut self) -> (&mut String, &mut String, &mut String) {
+        (&mut self.instance, &mut self.organization, &mut self.project)
+    }
 }
 
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdInstanceSerial {
     async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if let Some(cmd) = crate::cmd_id::by_name(ctx, self).await? {
+            return crate::cmd::Command::run(&cmd, ctx).await;
+        }
+
+        let until = match &self.until {
+            Some(until) => {
+                Some(regex::bytes::Regex::new(until).map_err(|err| anyhow!("invalid --until regex: {}", err))?)
+            }
+            None => None,
+        };
+        let follow = self.follow || until.is_some();
+
+        let mut output = match &self.output {
+            Some(path) => Some(RotatingFile::open(path, self.max_size, self.max_files)?),
+            None => None,
+        };
+        let mut writer = SerialWriter::new(self.strip_ansi, self.timestamps);
+
         let client = ctx.api_client("")?;
 
         let mut from_start = None;
@@ -510,37 +1670,240 @@ impl crate::cmd::Command for CmdInstanceSerial {
             None => most_recent = Some(16384),
         }
 
-        let mut cont = true;
-        while cont {
-            let output = client
-                .instances()
-                .serial_get(
+        // Listen for interrupts, so that Ctrl-C ends following cleanly.
+        let mut interrupted = tokio::spawn(tokio::signal::ctrl_c());
+
+        let cs = ctx.io.color_scheme();
+        let mut failures = 0;
+        let mut recent = Vec::new();
+        let mut matched = false;
+        // The output buffered by default, from before we started reading, may hold an
+        // old match, like the login prompt of an earlier boot. It is only matched
+        // against `--until` when the offset to read from was given explicitly.
+        let mut skip_match = self.byte_offset.is_none();
+        loop {
+            let result = tokio::select! {
+                result = client.instances().serial_get(
                     from_start,
                     &self.instance,
                     max_bytes,
                     most_recent,
                     &self.organization,
                     &self.project,
-                )
-                .await?;
+                ) => result,
+                _ = &mut interrupted => break,
+            };
+
+            let serial = match result {
+                Ok(serial) => {
+                    failures = 0;
+                    serial
+                }
+                Err(err) if follow && failures < SERIAL_MAX_RETRIES => {
+                    failures += 1;
+                    let delay = std::time::Duration::from_secs(1 << failures.min(5));
+                    writeln!(
+                        ctx.io.err_out,
+                        "{} Reading the serial console failed, retrying in {}s: {}",
+                        cs.warning_icon(),
+                        delay.as_secs(),
+                        err
+                    )?;
+
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => continue,
+                        _ = &mut interrupted => break,
+                    }
+                }
+                Err(err) => return Err(err),
+            };
 
-            std::io::stdout().write_all(&output.data)?;
+            let data = writer.process(&serial.data, chrono::Utc::now());
+            match &mut output {
+                Some(output) => output.write(&data)?,
+                None => ctx.io.out.write_all(&data)?,
+            }
+
+            if let Some(until) = until.as_ref().filter(|_| !skip_match) {
+                recent.extend_from_slice(&writer.strip(&serial.data));
+                if until.is_match(&recent) {
+                    matched = true;
+                    break;
+                }
+                if recent.len() > SERIAL_UNTIL_WINDOW {
+                    recent.drain(..recent.len() - SERIAL_UNTIL_WINDOW);
+                }
+            }
 
-            cont = self.continuous;
+            skip_match = false;
             most_recent = None;
-            from_start = Some(output.last_byte_offset);
+            from_start = Some(serial.last_byte_offset);
+
+            if !follow {
+                break;
+            }
 
-            if cont && output.data.is_empty() {
-                std::thread::sleep(std::time::Duration::from_secs(1));
+            if serial.data.is_empty() {
+                tokio::select! {
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(1)) => {}
+                    _ = &mut interrupted => break,
+                }
             }
         }
 
-        println!("\x1b[0m");
+        // Reset the terminal, in case the output left it in some odd state.
+        if output.is_none() && !self.strip_ansi {
+            writeln!(ctx.io.out, "\x1b[0m")?;
+        }
+
+        if until.is_some() && !matched {
+            return Err(anyhow!("stopped before the serial console output matched --until"));
+        }
 
         Ok(())
     }
 }
 
+/// Applies the processing asked for to the serial console output, as it is read.
+struct SerialWriter {
+    strip_ansi: bool,
+    timestamps: bool,
+    /// Matches ANSI escape sequences.
+    ansi: regex::bytes::Regex,
+    /// The start of an escape sequence that was cut off at the end of the last read,
+    /// held back until the rest of it comes in.
+    pending: Vec<u8>,
+    /// If the next byte starts a new line.
+    at_line_start: bool,
+}
+
+impl SerialWriter {
+    fn new(strip_ansi: bool, timestamps: bool) -> Self {
+        SerialWriter {
+            strip_ansi,
+            timestamps,
+            // CSI sequences, OSC sequences ended by BEL or ST, and two byte sequences.
+            ansi: regex::bytes::Regex::new(r"\x1b(\[[0-?]*[ -/]*[@-~]|\][^\x07\x1b]*(\x07|\x1b\\)|[@-Z\\^_])").unwrap(),
+            pending: Vec::new(),
+            at_line_start: true,
+        }
+    }
+
+    /// Process data read from the serial console at `now`, returning what to write.
+    fn process(&mut self, data: &[u8], now: chrono::DateTime<chrono::Utc>) -> Vec<u8> {
+        let data = if self.strip_ansi {
+            let mut buf = std::mem::take(&mut self.pending);
+            buf.extend_from_slice(data);
+
+            // Hold back an escape sequence that may be cut off at the end.
+            if let Some(start) = buf.iter().rposition(|b| *b == 0x1b) {
+                if !self.ansi.is_match(&buf[start..]) && buf.len() - start < 64 {
+                    self.pending = buf.split_off(start);
+                }
+            }
+
+            self.strip(&buf)
+        } else {
+            data.to_vec()
+        };
+
+        if !self.timestamps {
+            return data;
+        }
+
+        let timestamp = format!("{} ", now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true));
+        let mut processed = Vec::with_capacity(data.len());
+        for byte in data {
+            if self.at_line_start {
+                processed.extend_from_slice(timestamp.as_bytes());
+                self.at_line_start = false;
+            }
+            processed.push(byte);
+            if byte == b'\n' {
+                self.at_line_start = true;
+            }
+        }
+
+        processed
+    }
+
+    /// Returns the data with all complete ANSI escape sequences removed.
+    fn strip(&self, data: &[u8]) -> Vec<u8> {
+        self.ansi.replace_all(data, &b""[..]).into_owned()
+    }
+}
+
+/// A file that is rotated once it grows past a size.
+struct RotatingFile {
+    path: std::path::PathBuf,
+    max_size: u64,
+    max_files: usize,
+    file: std::fs::File,
+    size: u64,
+}
+
+impl RotatingFile {
+    /// Open the file for appending, creating it if it doesn't exist.
+    fn open(path: &std::path::Path, max_size: u64, max_files: usize) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| anyhow!("opening {} failed: {}", path.display(), err))?;
+        let size = file.metadata()?.len();
+
+        Ok(RotatingFile {
+            path: path.to_path_buf(),
+            max_size,
+            max_files,
+            file,
+            size,
+        })
+    }
+
+    /// Write the data to the file, rotating it first if the data would take it past
+    /// the maximum size.
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        if self.max_size > 0 && self.size > 0 && self.size + data.len() as u64 > self.max_size {
+            self.rotate()?;
+        }
+
+        self.file.write_all(data)?;
+        self.size += data.len() as u64;
+
+        Ok(())
+    }
+
+    /// Shift each rotated file up by one, dropping the oldest, and start a new file.
+    fn rotate(&mut self) -> Result<()> {
+        if self.max_files > 0 {
+            for n in (1..self.max_files).rev() {
+                match std::fs::rename(self.rotated_path(n), self.rotated_path(n + 1)) {
+                    Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+                    _ => {}
+                }
+            }
+            std::fs::rename(&self.path, self.rotated_path(1))?;
+        }
+
+        self.file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+
+    /// Returns the path of the `n`th rotated file, like `boot.log.1`.
+    fn rotated_path(&self, n: usize) -> std::path::PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", n));
+        path.into()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
@@ -697,6 +2060,141 @@ mod test {
                 want_out: "".to_string(),
                 want_err: "--confirm required when not running interactively".to_string(),
             },
+            TestItem {
+                name: "edit no file or editor".to_string(),
+                cmd: crate::cmd_instance::SubCommand::Edit(crate::cmd_instance::CmdInstanceEdit {
+                    instance: "things".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    from_file: "".to_string(),
+                    editor: false,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--from-file or --editor required".to_string(),
+            },
+            TestItem {
+                name: "edit editor non-interactive".to_string(),
+                cmd: crate::cmd_instance::SubCommand::Edit(crate::cmd_instance::CmdInstanceEdit {
+                    instance: "things".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    from_file: "".to_string(),
+                    editor: true,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--editor requires a terminal, use --from-file instead".to_string(),
+            },
+            TestItem {
+                name: "cp no instance".to_string(),
+                cmd: crate::cmd_instance::SubCommand::Cp(crate::cmd_instance::CmdInstanceCp {
+                    source: "./app.tar.gz".to_string(),
+                    destination: "/tmp/".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    user: "alice".to_string(),
+                    recursive: false,
+                    internal: false,
+                    ssh_flags: vec![],
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "one of the source and destination must be a path on an instance, like my-instance:/tmp"
+                    .to_string(),
+            },
+            TestItem {
+                name: "cp between instances".to_string(),
+                cmd: crate::cmd_instance::SubCommand::Cp(crate::cmd_instance::CmdInstanceCp {
+                    source: "things:/tmp/a".to_string(),
+                    destination: "stuff:/tmp/".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    user: "alice".to_string(),
+                    recursive: false,
+                    internal: false,
+                    ssh_flags: vec![],
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "copying between two instances is not supported".to_string(),
+            },
+            TestItem {
+                name: "exec parallel zero".to_string(),
+                cmd: crate::cmd_instance::SubCommand::Exec(crate::cmd_instance::CmdInstanceExec {
+                    command: vec!["uptime".to_string()],
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    selector: "name=web-*".to_string(),
+                    parallel: 0,
+                    user: "alice".to_string(),
+                    internal: false,
+                    ssh_flags: vec![],
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--parallel must be greater than 0".to_string(),
+            },
+            TestItem {
+                name: "exec invalid selector".to_string(),
+                cmd: crate::cmd_instance::SubCommand::Exec(crate::cmd_instance::CmdInstanceExec {
+                    command: vec!["uptime".to_string()],
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    selector: "web-*".to_string(),
+                    parallel: 10,
+                    user: "alice".to_string(),
+                    internal: false,
+                    ssh_flags: vec![],
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "invalid selector condition web-*, expected field=pattern".to_string(),
+            },
+            TestItem {
+                name: "start by name no organization".to_string(),
+                cmd: crate::cmd_instance::SubCommand::Start(crate::cmd_instance::CmdInstanceStart {
+                    instance: "things".to_string(),
+                    organization: "".to_string(),
+                    project: "foo".to_string(),
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "-o|--organization required when the instance is given by name".to_string(),
+            },
+            TestItem {
+                name: "migrate no sled non-interactive".to_string(),
+                cmd: crate::cmd_instance::SubCommand::Migrate(crate::cmd_instance::CmdInstanceMigrate {
+                    instance: "things".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    to_sled: "".to_string(),
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "-s|--to-sled required in non-interactive mode".to_string(),
+            },
+            TestItem {
+                name: "migrate invalid sled id".to_string(),
+                cmd: crate::cmd_instance::SubCommand::Migrate(crate::cmd_instance::CmdInstanceMigrate {
+                    instance: "things".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    to_sled: "sled-1".to_string(),
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "sled must be an ID, got sled-1".to_string(),
+            },
             TestItem {
                 name: "list zero limit".to_string(),
                 cmd: crate::cmd_instance::SubCommand::List(crate::cmd_instance::CmdInstanceList {
@@ -754,4 +2252,155 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_format_external_ips() {
+        let external_ips = vec![
+            oxide_api::types::ExternalIp {
+                ip: "172.20.15.227".parse().unwrap(),
+                kind: oxide_api::types::IpKind::Ephemeral,
+            },
+            oxide_api::types::ExternalIp {
+                ip: "fd00:1122:3344:101::5".parse().unwrap(),
+                kind: oxide_api::types::IpKind::Floating,
+            },
+        ];
+
+        assert_eq!(crate::cmd_instance::format_external_ips(&[]), "");
+        assert_eq!(
+            crate::cmd_instance::format_external_ips(&external_ips),
+            "172.20.15.227, fd00:1122:3344:101::5"
+        );
+    }
+
+    #[test]
+    fn test_ssh_flag_args() {
+        let flags = vec!["-L 80:localhost:80".to_string(), "-o 'ProxyJump bastion'".to_string()];
+        assert_eq!(
+            crate::cmd_instance::ssh_flag_args(&flags).unwrap(),
+            vec!["-L", "80:localhost:80", "-o", "ProxyJump bastion"]
+        );
+
+        let err = crate::cmd_instance::ssh_flag_args(&["-o 'unterminated".to_string()]).unwrap_err();
+        assert_eq!(err.to_string(), "invalid --ssh-flag: -o 'unterminated");
+    }
+
+    #[test]
+    fn test_parse_remote() {
+        assert_eq!(
+            crate::cmd_instance::parse_remote("things:/tmp/app.tar.gz"),
+            Some(("things", "/tmp/app.tar.gz"))
+        );
+        assert_eq!(crate::cmd_instance::parse_remote("things:"), Some(("things", "")));
+        assert_eq!(crate::cmd_instance::parse_remote("app.tar.gz"), None);
+        assert_eq!(crate::cmd_instance::parse_remote("./a:b"), None);
+        assert_eq!(crate::cmd_instance::parse_remote("/tmp/a:b"), None);
+        assert_eq!(crate::cmd_instance::parse_remote(":b"), None);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        use crate::cmd_instance::glob_match;
+
+        assert!(glob_match("web-*", "web-1"));
+        assert!(glob_match("web-*", "web-"));
+        assert!(glob_match("*-db", "main-db"));
+        assert!(glob_match("w?b-*-?", "web-east-1"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(!glob_match("web-*", "db-1"));
+        assert!(!glob_match("web-?", "web-10"));
+        assert!(!glob_match("a*b*c", "aXbYbZ"));
+        assert!(!glob_match("web", "web-1"));
+    }
+
+    #[test]
+    fn test_selector() {
+        use crate::cmd_instance::{parse_selector, selector_matches};
+
+        assert!(parse_selector("").unwrap().is_empty());
+        assert_eq!(
+            parse_selector("name=web-*, run_state=running").unwrap(),
+            vec![
+                ("name".to_string(), "web-*".to_string()),
+                ("run_state".to_string(), "running".to_string())
+            ]
+        );
+        assert_eq!(
+            parse_selector("=web").unwrap_err().to_string(),
+            "invalid selector condition =web, expected field=pattern"
+        );
+
+        let instance = serde_json::json!({
+            "name": "web-1",
+            "run_state": "running",
+            "ncpus": 2,
+        });
+        assert!(selector_matches(&[], &instance).unwrap());
+        assert!(selector_matches(&parse_selector("name=web-*,ncpus=2").unwrap(), &instance).unwrap());
+        assert!(!selector_matches(&parse_selector("name=web-*,run_state=stopped").unwrap(), &instance).unwrap());
+        assert_eq!(
+            selector_matches(&parse_selector("label=web").unwrap(), &instance)
+                .unwrap_err()
+                .to_string(),
+            "instances have no field label to select by"
+        );
+    }
+
+    #[test]
+    fn test_serial_writer() {
+        use super::SerialWriter;
+
+        let now = chrono::DateTime::parse_from_rfc3339("2022-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+
+        let mut writer = SerialWriter::new(false, false);
+        assert_eq!(writer.process(b"\x1b[1mboot\x1b[0m\n", now), b"\x1b[1mboot\x1b[0m\n");
+
+        let mut writer = SerialWriter::new(true, false);
+        assert_eq!(writer.process(b"\x1b[1;32mboot\x1b[0m ok\n", now), b"boot ok\n");
+        assert_eq!(writer.process(b"\x1b]0;title\x07done\x1b[", now), b"done");
+        assert_eq!(writer.process(b"2Kagain\n", now), b"again\n");
+
+        let mut writer = SerialWriter::new(false, true);
+        assert_eq!(
+            writer.process(b"one\ntw", now),
+            b"2022-06-01T12:00:00.000Z one\n2022-06-01T12:00:00.000Z tw"
+        );
+        assert_eq!(writer.process(b"o\n", now), b"o\n");
+    }
+
+    #[test]
+    fn test_rotating_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("serial.log");
+        let read = |n: usize| {
+            let path = if n == 0 {
+                path.clone()
+            } else {
+                dir.path().join(format!("serial.log.{}", n))
+            };
+            std::fs::read_to_string(path).ok()
+        };
+
+        let mut file = super::RotatingFile::open(&path, 8, 2).unwrap();
+        file.write(b"aaaa").unwrap();
+        file.write(b"bbbb").unwrap();
+        assert_eq!(read(0).unwrap(), "aaaabbbb");
+        assert_eq!(read(1), None);
+
+        file.write(b"cccc").unwrap();
+        file.write(b"dddddddd").unwrap();
+        file.write(b"ee").unwrap();
+        assert_eq!(read(0).unwrap(), "ee");
+        assert_eq!(read(1).unwrap(), "dddddddd");
+        assert_eq!(read(2).unwrap(), "cccc");
+        assert_eq!(read(3), None);
+
+        // Reopening appends to what is already there.
+        let mut file = super::RotatingFile::open(&path, 8, 2).unwrap();
+        file.write(b"ff").unwrap();
+        assert_eq!(read(0).unwrap(), "eeff");
+    }
 }
@@ -0,0 +1,753 @@
+use std::{io::Write, net::IpAddr};
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+
+/// Create, list, edit, view, and delete the network interfaces of an instance.
+///
+/// The first network interface of an instance is its primary interface. Additional
+/// interfaces can be attached to other VPCs and subnets.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdInstanceNic {
+    #[clap(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser, Debug, Clone)]
+enum SubCommand {
+    Create(CmdInstanceNicCreate),
+    Delete(CmdInstanceNicDelete),
+    Edit(CmdInstanceNicEdit),
+    List(CmdInstanceNicList),
+    View(CmdInstanceNicView),
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdInstanceNic {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        match &self.subcmd {
+            SubCommand::Create(cmd) => cmd.run(ctx).await,
+            SubCommand::Delete(cmd) => cmd.run(ctx).await,
+            SubCommand::Edit(cmd) => cmd.run(ctx).await,
+            SubCommand::List(cmd) => cmd.run(ctx).await,
+            SubCommand::View(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+/// Create a network interface on an instance.
+///
+/// If the VPC or subnet are not given, you will be prompted to select them. The IP
+/// address of the interface is assigned automatically unless `--ip` is given, in which
+/// case it must be within the subnet.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdInstanceNicCreate {
+    /// The name of the network interface to create.
+    #[clap(name = "nic", required = true)]
+    pub nic: String,
+
+    /// The instance to create the network interface on.
+    #[clap(long, short, required = true)]
+    pub instance: String,
+
+    /// The project that holds the instance.
+    #[clap(long, short, required = true)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// The description for the network interface.
+    #[clap(long = "description", short = 'D', default_value = "")]
+    pub description: String,
+
+    /// The VPC to create the network interface in.
+    #[clap(long, short, default_value = "")]
+    pub vpc: String,
+
+    /// The subnet to create the network interface in.
+    #[clap(long, short, default_value = "")]
+    pub subnet: String,
+
+    /// The IP address for the network interface. One is assigned automatically if not given.
+    #[clap(long)]
+    pub ip: Option<IpAddr>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdInstanceNicCreate {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let mut description = self.description.clone();
+        let mut vpc = self.vpc.clone();
+        let mut subnet = self.subnet.clone();
+
+        if description.is_empty() && !ctx.io.can_prompt() {
+            return Err(anyhow!("-D|--description required in non-interactive mode"));
+        }
+
+        if vpc.is_empty() && !ctx.io.can_prompt() {
+            return Err(anyhow!("-v|--vpc required in non-interactive mode"));
+        }
+
+        if subnet.is_empty() && !ctx.io.can_prompt() {
+            return Err(anyhow!("-s|--subnet required in non-interactive mode"));
+        }
+
+        let client = ctx.api_client("")?;
+
+        if description.is_empty() {
+            match dialoguer::Input::<String>::new()
+                .with_prompt("network interface description:")
+                .interact_text()
+            {
+                Ok(input) => description = input,
+                Err(err) => {
+                    return Err(anyhow!("prompt failed: {}", err));
+                }
+            }
+        }
+
+        // If they didn't specify a VPC, prompt for it.
+        if vpc.is_empty() {
+            let vpcs: Vec<String> = client
+                .vpcs()
+                .get_all(
+                    &self.organization,
+                    &self.project,
+                    oxide_api::types::NameSortMode::NameAscending,
+                )
+                .await?
+                .into_iter()
+                .map(|v| v.name)
+                .collect();
+
+            if vpcs.is_empty() {
+                return Err(anyhow!("no VPCs found in {}/{}", self.organization, self.project));
+            }
+
+            match dialoguer::Select::new()
+                .with_prompt("Select VPC:")
+                .items(&vpcs)
+                .interact()
+            {
+                Ok(index) => vpc = vpcs[index].to_string(),
+                Err(err) => {
+                    return Err(anyhow!("prompt failed: {}", err));
+                }
+            }
+        }
+
+        // If they didn't specify a subnet, prompt for it from the subnets of the VPC.
+        if subnet.is_empty() {
+            let subnets = client
+                .subnets()
+                .get_all(
+                    &self.organization,
+                    &self.project,
+                    oxide_api::types::NameSortMode::NameAscending,
+                    &vpc,
+                )
+                .await?;
+
+            if subnets.is_empty() {
+                return Err(anyhow!("no subnets found in VPC {}", vpc));
+            }
+
+            let items: Vec<String> = subnets
+                .iter()
+                .map(|s| format!("{} ({}, {})", s.name, s.ipv4_block, s.ipv6_block))
+                .collect();
+
+            match dialoguer::Select::new()
+                .with_prompt("Select subnet:")
+                .items(&items)
+                .interact()
+            {
+                Ok(index) => subnet = subnets[index].name.to_string(),
+                Err(err) => {
+                    return Err(anyhow!("prompt failed: {}", err));
+                }
+            }
+        }
+
+        // Make sure the address they asked for can actually be assigned in the subnet
+        // before we try to create the interface.
+        if let Some(ip) = self.ip {
+            let s = client
+                .subnets()
+                .get(&self.organization, &self.project, &subnet, &vpc)
+                .await?;
+            validate_ip_in_subnet(ip, &s.ipv4_block.0, &s.ipv6_block.0, &subnet)?;
+        }
+
+        client
+            .instances()
+            .network_interfaces_post(
+                &self.instance,
+                &self.organization,
+                &self.project,
+                &oxide_api::types::NetworkInterfaceCreate {
+                    description,
+                    ip: self.ip,
+                    name: self.nic.to_string(),
+                    subnet_name: subnet.to_string(),
+                    vpc_name: vpc.to_string(),
+                },
+            )
+            .await?;
+
+        let cs = ctx.io.color_scheme();
+        let full_name = format!("{}/{}", self.organization, self.project);
+        writeln!(
+            ctx.io.out,
+            "{} Created network interface {} on instance {} in {}",
+            cs.success_icon(),
+            self.nic,
+            self.instance,
+            full_name
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Delete a network interface from an instance.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdInstanceNicDelete {
+    /// The network interface to delete.
+    #[clap(name = "nic", required = true)]
+    pub nic: String,
+
+    /// The instance that holds the network interface.
+    #[clap(long, short, required = true)]
+    pub instance: String,
+
+    /// The project that holds the instance.
+    #[clap(long, short, required = true)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// Confirm deletion without prompting.
+    #[clap(long)]
+    pub confirm: bool,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdInstanceNicDelete {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if !ctx.io.can_prompt() && !self.confirm {
+            return Err(anyhow!("--confirm required when not running interactively"));
+        }
+
+        let client = ctx.api_client("")?;
+
+        // Confirm deletion.
+        if !self.confirm {
+            if let Err(err) = dialoguer::Input::<String>::new()
+                .with_prompt(format!("Type {} to confirm deletion:", self.nic))
+                .validate_with(|input: &String| -> Result<(), &str> {
+                    if input.trim() == self.nic {
+                        Ok(())
+                    } else {
+                        Err("mismatched confirmation")
+                    }
+                })
+                .interact_text()
+            {
+                return Err(anyhow!("prompt failed: {}", err));
+            }
+        }
+
+        client
+            .instances()
+            .network_interfaces_delete_interface(&self.instance, &self.nic, &self.organization, &self.project)
+            .await?;
+
+        let cs = ctx.io.color_scheme();
+        let full_name = format!("{}/{}", self.organization, self.project);
+        writeln!(
+            ctx.io.out,
+            "{} Deleted network interface {} from instance {} in {}",
+            cs.success_icon_with_color(ansi_term::Color::Red),
+            self.nic,
+            self.instance,
+            full_name
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Edit network interface settings.
+///
+/// Making a secondary interface the primary takes effect the next time the instance
+/// is rebooted. The IP address of an interface cannot be changed, create a new
+/// interface instead.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdInstanceNicEdit {
+    /// The network interface to edit.
+    #[clap(name = "nic", required = true)]
+    pub nic: String,
+
+    /// The instance that holds the network interface.
+    #[clap(long, short, required = true)]
+    pub instance: String,
+
+    /// The project that holds the instance.
+    #[clap(long, short, required = true)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// The new name for the network interface.
+    #[clap(long = "name", short = 'n', default_value = "")]
+    pub new_name: String,
+
+    /// The new description for the network interface.
+    #[clap(long = "description", short = 'D', default_value = "")]
+    pub description: String,
+
+    /// Make the network interface the primary interface of the instance.
+    #[clap(long)]
+    pub primary: bool,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdInstanceNicEdit {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if self.new_name.is_empty() && self.description.is_empty() && !self.primary {
+            return Err(anyhow!("nothing to edit"));
+        }
+
+        let client = ctx.api_client("")?;
+
+        client
+            .instances()
+            .network_interfaces_put_interface(
+                &self.instance,
+                &self.nic,
+                &self.organization,
+                &self.project,
+                &oxide_api::types::NetworkInterfaceUpdate {
+                    description: self.description.clone(),
+                    name: self.new_name.clone(),
+                    primary: self.primary,
+                },
+            )
+            .await?;
+
+        let cs = ctx.io.color_scheme();
+        let full_name = format!("{}/{}", self.organization, self.project);
+        if !self.new_name.is_empty() {
+            writeln!(
+                ctx.io.out,
+                "{} Edited network interface {} -> {} on instance {} in {}",
+                cs.success_icon(),
+                self.nic,
+                self.new_name,
+                self.instance,
+                full_name
+            )?;
+        } else {
+            writeln!(
+                ctx.io.out,
+                "{} Edited network interface {} on instance {} in {}",
+                cs.success_icon_with_color(ansi_term::Color::Red),
+                self.nic,
+                self.instance,
+                full_name
+            )?;
+        }
+
+        if self.primary {
+            writeln!(
+                ctx.io.out,
+                "Reboot instance {} for the new primary interface to take effect",
+                self.instance
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// List the network interfaces of an instance.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdInstanceNicList {
+    /// The instance to list the network interfaces of.
+    #[clap(long, short, required = true)]
+    pub instance: String,
+
+    /// The project that holds the instance.
+    #[clap(long, short, required = true)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// Maximum number of items to list.
+    #[clap(long, short, default_value = "30")]
+    pub limit: u32,
+
+    /// Make additional HTTP requests to fetch all pages.
+    #[clap(long)]
+    pub paginate: bool,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdInstanceNicList {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if self.limit < 1 {
+            return Err(anyhow!("--limit must be greater than 0"));
+        }
+
+        let client = ctx.api_client("")?;
+
+        let results = if self.paginate {
+            client
+                .instances()
+                .network_interfaces_get_all(
+                    &self.instance,
+                    &self.organization,
+                    &self.project,
+                    oxide_api::types::NameSortMode::NameAscending,
+                )
+                .await?
+        } else {
+            client
+                .instances()
+                .network_interfaces_get(
+                    &self.instance,
+                    self.limit,
+                    &self.organization,
+                    "",
+                    &self.project,
+                    oxide_api::types::NameSortMode::NameAscending,
+                )
+                .await?
+        };
+
+        let format = ctx.format(&self.format)?;
+        ctx.io.write_output_for_vec(&format, &results)?;
+        Ok(())
+    }
+}
+
+/// View a network interface of an instance.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdInstanceNicView {
+    /// The network interface to view.
+    #[clap(name = "nic", required = true)]
+    pub nic: String,
+
+    /// The instance that holds the network interface.
+    #[clap(long, short, required = true)]
+    pub instance: String,
+
+    /// The project that holds the instance.
+    #[clap(long, short, required = true)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdInstanceNicView {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let client = ctx.api_client("")?;
+
+        let result = client
+            .instances()
+            .network_interfaces_get_interface(&self.instance, &self.nic, &self.organization, &self.project)
+            .await?;
+
+        let format = ctx.format(&self.format)?;
+        ctx.io.write_output(&format, &result)?;
+        Ok(())
+    }
+}
+
+/// The number of addresses reserved by the control plane at the start of every
+/// subnet, the broadcast address at the end is reserved too.
+pub const RESERVED_SUBNET_ADDRESSES: u64 = 5;
+
+/// Returns if the address in the IPv4 block is reserved by the control plane, and so
+/// can't be assigned to an interface.
+pub fn is_reserved_ipv4(block: &ipnetwork::Ipv4Network, ip: std::net::Ipv4Addr) -> bool {
+    let offset = u32::from(ip).wrapping_sub(u32::from(block.network())) as u64;
+
+    offset < RESERVED_SUBNET_ADDRESSES || ip == block.broadcast()
+}
+
+/// Checks that an address can be assigned to an interface in the subnet with the given
+/// blocks: it must be within one of them, and not be reserved in the IPv4 block.
+fn validate_ip_in_subnet(
+    ip: IpAddr,
+    ipv4_block: &ipnetwork::Ipv4Network,
+    ipv6_block: &ipnetwork::Ipv6Network,
+    subnet: &str,
+) -> Result<()> {
+    match ip {
+        IpAddr::V4(ip) => {
+            if !ipv4_block.contains(ip) {
+                return Err(anyhow!(
+                    "address {} is not in the IPv4 block {} of subnet {}",
+                    ip,
+                    ipv4_block,
+                    subnet
+                ));
+            }
+
+            if is_reserved_ipv4(ipv4_block, ip) {
+                return Err(anyhow!(
+                    "address {} is reserved in the IPv4 block {} of subnet {}",
+                    ip,
+                    ipv4_block,
+                    subnet
+                ));
+            }
+        }
+        IpAddr::V6(ip) => {
+            if !ipv6_block.contains(ip) {
+                return Err(anyhow!(
+                    "address {} is not in the IPv6 block {} of subnet {}",
+                    ip,
+                    ipv6_block,
+                    subnet
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::cmd::Command;
+
+    pub struct TestItem {
+        name: String,
+        cmd: crate::cmd_instance_nic::SubCommand,
+        stdin: String,
+        want_out: String,
+        want_err: String,
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_cmd_instance_nic() {
+        let tests: Vec<TestItem> = vec![
+            TestItem {
+                name: "create no description".to_string(),
+                cmd: crate::cmd_instance_nic::SubCommand::Create(crate::cmd_instance_nic::CmdInstanceNicCreate {
+                    nic: "net1".to_string(),
+                    instance: "things".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    description: "".to_string(),
+                    vpc: "default".to_string(),
+                    subnet: "default".to_string(),
+                    ip: None,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "-D|--description required in non-interactive mode".to_string(),
+            },
+            TestItem {
+                name: "create no vpc".to_string(),
+                cmd: crate::cmd_instance_nic::SubCommand::Create(crate::cmd_instance_nic::CmdInstanceNicCreate {
+                    nic: "net1".to_string(),
+                    instance: "things".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    description: "second nic".to_string(),
+                    vpc: "".to_string(),
+                    subnet: "default".to_string(),
+                    ip: None,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "-v|--vpc required in non-interactive mode".to_string(),
+            },
+            TestItem {
+                name: "create no subnet".to_string(),
+                cmd: crate::cmd_instance_nic::SubCommand::Create(crate::cmd_instance_nic::CmdInstanceNicCreate {
+                    nic: "net1".to_string(),
+                    instance: "things".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    description: "second nic".to_string(),
+                    vpc: "default".to_string(),
+                    subnet: "".to_string(),
+                    ip: None,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "-s|--subnet required in non-interactive mode".to_string(),
+            },
+            TestItem {
+                name: "delete no --confirm non-interactive".to_string(),
+                cmd: crate::cmd_instance_nic::SubCommand::Delete(crate::cmd_instance_nic::CmdInstanceNicDelete {
+                    nic: "net1".to_string(),
+                    instance: "things".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    confirm: false,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--confirm required when not running interactively".to_string(),
+            },
+            TestItem {
+                name: "edit nothing".to_string(),
+                cmd: crate::cmd_instance_nic::SubCommand::Edit(crate::cmd_instance_nic::CmdInstanceNicEdit {
+                    nic: "net1".to_string(),
+                    instance: "things".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    new_name: "".to_string(),
+                    description: "".to_string(),
+                    primary: false,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "nothing to edit".to_string(),
+            },
+            TestItem {
+                name: "list zero limit".to_string(),
+                cmd: crate::cmd_instance_nic::SubCommand::List(crate::cmd_instance_nic::CmdInstanceNicList {
+                    instance: "things".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    limit: 0,
+                    paginate: false,
+                    format: None,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--limit must be greater than 0".to_string(),
+            },
+        ];
+
+        let mut config = crate::config::new_blank_config().unwrap();
+        let mut c = crate::config_from_env::EnvConfig::inherit_env(&mut config);
+
+        for t in tests {
+            let (mut io, stdout_path, stderr_path) = crate::iostreams::IoStreams::test();
+            if !t.stdin.is_empty() {
+                io.stdin = Box::new(std::io::Cursor::new(t.stdin));
+            }
+            // We need to also turn off the fancy terminal colors.
+            // This ensures it also works in GitHub actions/any CI.
+            io.set_color_enabled(false);
+            io.set_never_prompt(true);
+            let mut ctx = crate::context::Context {
+                config: &mut c,
+                io,
+                debug: false,
+            };
+
+            let cmd_instance_nic = crate::cmd_instance_nic::CmdInstanceNic { subcmd: t.cmd };
+            match cmd_instance_nic.run(&mut ctx).await {
+                Ok(()) => {
+                    let stdout = std::fs::read_to_string(stdout_path).unwrap();
+                    let stderr = std::fs::read_to_string(stderr_path).unwrap();
+                    assert!(stderr.is_empty(), "test {}: {}", t.name, stderr);
+                    if !stdout.contains(&t.want_out) {
+                        assert_eq!(stdout, t.want_out, "test {}: stdout mismatch", t.name);
+                    }
+                }
+                Err(err) => {
+                    let stdout = std::fs::read_to_string(stdout_path).unwrap();
+                    let stderr = std::fs::read_to_string(stderr_path).unwrap();
+                    assert_eq!(stdout, t.want_out, "test {}", t.name);
+                    if !err.to_string().contains(&t.want_err) {
+                        assert_eq!(err.to_string(), t.want_err, "test {}: err mismatch", t.name);
+                    }
+                    assert!(stderr.is_empty(), "test {}: {}", t.name, stderr);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_reserved_ipv4() {
+        let block = ipnetwork::Ipv4Network::from_str("172.30.0.0/22").unwrap();
+        let reserved =
+            |ip: &str| crate::cmd_instance_nic::is_reserved_ipv4(&block, std::net::Ipv4Addr::from_str(ip).unwrap());
+
+        assert!(reserved("172.30.0.0"));
+        assert!(reserved("172.30.0.1"));
+        assert!(reserved("172.30.0.4"));
+        assert!(!reserved("172.30.0.5"));
+        assert!(!reserved("172.30.3.254"));
+        assert!(reserved("172.30.3.255"));
+    }
+
+    #[test]
+    fn test_validate_ip_in_subnet() {
+        let ipv4_block = ipnetwork::Ipv4Network::from_str("172.30.0.0/22").unwrap();
+        let ipv6_block = ipnetwork::Ipv6Network::from_str("fd00:1122:3344:0100::/64").unwrap();
+
+        let check = |ip: &str| {
+            crate::cmd_instance_nic::validate_ip_in_subnet(
+                std::net::IpAddr::from_str(ip).unwrap(),
+                &ipv4_block,
+                &ipv6_block,
+                "default",
+            )
+        };
+
+        assert!(check("172.30.0.10").is_ok());
+        assert!(check("fd00:1122:3344:100::10").is_ok());
+        assert_eq!(
+            check("172.30.4.1").unwrap_err().to_string(),
+            "address 172.30.4.1 is not in the IPv4 block 172.30.0.0/22 of subnet default"
+        );
+        assert_eq!(
+            check("172.30.3.255").unwrap_err().to_string(),
+            "address 172.30.3.255 is reserved in the IPv4 block 172.30.0.0/22 of subnet default"
+        );
+        assert_eq!(
+            check("172.30.0.1").unwrap_err().to_string(),
+            "address 172.30.0.1 is reserved in the IPv4 block 172.30.0.0/22 of subnet default"
+        );
+        assert_eq!(
+            check("fd00:1122:3344:200::10").unwrap_err().to_string(),
+            "address fd00:1122:3344:200::10 is not in the IPv6 block fd00:1122:3344:100::/64 of subnet default"
+        );
+    }
+}
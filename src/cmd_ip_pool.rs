@@ -0,0 +1,488 @@
+use std::{io::Write, net::IpAddr, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use cli_macro::crud_gen;
+
+/// Create, list, edit, view, and delete IP pools.
+///
+/// Additionally, list, add, and remove the address ranges of an IP pool.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdIpPool {
+    #[clap(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[crud_gen {
+    tag = "ip-pools",
+}]
+#[derive(Parser, Debug, Clone)]
+enum SubCommand {
+    #[clap(alias = "ranges")]
+    Range(CmdIpPoolRange),
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdIpPool {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        match &self.subcmd {
+            SubCommand::Create(cmd) => cmd.run(ctx).await,
+            SubCommand::Delete(cmd) => cmd.run(ctx).await,
+            SubCommand::Edit(cmd) => cmd.run(ctx).await,
+            SubCommand::List(cmd) => cmd.run(ctx).await,
+            SubCommand::Range(cmd) => cmd.run(ctx).await,
+            SubCommand::View(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+/// List, add, and remove the address ranges of an IP pool.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdIpPoolRange {
+    #[clap(subcommand)]
+    subcmd: RangeSubCommand,
+}
+
+#[derive(Parser, Debug, Clone)]
+enum RangeSubCommand {
+    Add(CmdIpPoolRangeAdd),
+    List(CmdIpPoolRangeList),
+    Remove(CmdIpPoolRangeRemove),
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdIpPoolRange {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        match &self.subcmd {
+            RangeSubCommand::Add(cmd) => cmd.run(ctx).await,
+            RangeSubCommand::List(cmd) => cmd.run(ctx).await,
+            RangeSubCommand::Remove(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+/// Add an address range to an IP pool.
+///
+/// The range can be given as a CIDR block, like `10.0.0.0/24`, or as an inclusive
+/// `first-last` pair of addresses, like `10.0.0.10-10.0.0.20`.
+///
+/// Ranges that overlap a range already in the pool are refused.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdIpPoolRangeAdd {
+    /// The IP pool to add the range to.
+    #[clap(name = "ip-pool", required = true)]
+    pub ip_pool: String,
+
+    /// The range of addresses to add.
+    #[clap(name = "range", required = true)]
+    pub range: AddressRange,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdIpPoolRangeAdd {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let client = ctx.api_client("")?;
+
+        // Make sure the range does not overlap anything already in the pool, the API
+        // would otherwise give us a much less helpful error.
+        let existing = client.ip_pools().ranges_get_all(&self.ip_pool).await?;
+        self.range
+            .ensure_no_overlap(&existing, &format!("IP pool {}", self.ip_pool))?;
+
+        client
+            .ip_pools()
+            .ranges_add(&self.ip_pool, &self.range.to_ip_range())
+            .await?;
+
+        let cs = ctx.io.color_scheme();
+        writeln!(
+            ctx.io.out,
+            "{} Added range {} to IP pool {}",
+            cs.success_icon(),
+            self.range,
+            self.ip_pool
+        )?;
+
+        Ok(())
+    }
+}
+
+/// List the address ranges of an IP pool.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdIpPoolRangeList {
+    /// The IP pool to list the ranges for.
+    #[clap(name = "ip-pool", required = true)]
+    pub ip_pool: String,
+
+    /// Maximum number of ranges to list.
+    #[clap(long, short, default_value = "30")]
+    pub limit: u32,
+
+    /// Make additional HTTP requests to fetch all pages.
+    #[clap(long)]
+    pub paginate: bool,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdIpPoolRangeList {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if self.limit < 1 {
+            return Err(anyhow!("--limit must be greater than 0"));
+        }
+
+        let client = ctx.api_client("")?;
+
+        let results = if self.paginate {
+            client.ip_pools().ranges_get_all(&self.ip_pool).await?
+        } else {
+            client.ip_pools().ranges_get(self.limit, "", &self.ip_pool).await?
+        };
+
+        let format = ctx.format(&self.format)?;
+        ctx.io.write_output_for_vec(&format, &results)?;
+        Ok(())
+    }
+}
+
+/// Remove an address range from an IP pool.
+///
+/// The range must match a range in the pool exactly. It can be given as a CIDR
+/// block or as an inclusive `first-last` pair of addresses.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdIpPoolRangeRemove {
+    /// The IP pool to remove the range from.
+    #[clap(name = "ip-pool", required = true)]
+    pub ip_pool: String,
+
+    /// The range of addresses to remove.
+    #[clap(name = "range", required = true)]
+    pub range: AddressRange,
+
+    /// Confirm removal without prompting.
+    #[clap(long)]
+    pub confirm: bool,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdIpPoolRangeRemove {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if !ctx.io.can_prompt() && !self.confirm {
+            return Err(anyhow!("--confirm required when not running interactively"));
+        }
+
+        let client = ctx.api_client("")?;
+
+        // Confirm removal.
+        if !self.confirm {
+            let range = self.range.to_string();
+            if let Err(err) = dialoguer::Input::<String>::new()
+                .with_prompt(format!("Type {} to confirm removal:", range))
+                .validate_with(|input: &String| -> Result<(), &str> {
+                    if input.trim() == range {
+                        Ok(())
+                    } else {
+                        Err("mismatched confirmation")
+                    }
+                })
+                .interact_text()
+            {
+                return Err(anyhow!("prompt failed: {}", err));
+            }
+        }
+
+        client
+            .ip_pools()
+            .ranges_delete(&self.ip_pool, &self.range.to_ip_range())
+            .await?;
+
+        let cs = ctx.io.color_scheme();
+        writeln!(
+            ctx.io.out,
+            "{} Removed range {} from IP pool {}",
+            cs.success_icon_with_color(ansi_term::Color::Red),
+            self.range,
+            self.ip_pool
+        )?;
+
+        Ok(())
+    }
+}
+
+/// An inclusive range of IP addresses.
+///
+/// This parses from either a CIDR block or a `first-last` pair of addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressRange {
+    pub first: IpAddr,
+    pub last: IpAddr,
+}
+
+impl AddressRange {
+    /// Returns if the two ranges share any addresses.
+    pub fn overlaps(&self, other: &AddressRange) -> bool {
+        self.first.is_ipv4() == other.first.is_ipv4() && self.first <= other.last && other.first <= self.last
+    }
+
+    /// Returns an error if the range overlaps any of the existing ranges of a pool.
+    pub fn ensure_no_overlap(&self, existing: &[oxide_api::types::IpPoolRange], pool: &str) -> Result<()> {
+        for r in existing {
+            let existing_range = AddressRange::from(&r.range);
+            if self.overlaps(&existing_range) {
+                return Err(anyhow!(
+                    "range {} overlaps existing range {} in {}",
+                    self,
+                    existing_range,
+                    pool
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Convert the range into the type the API expects.
+    pub fn to_ip_range(&self) -> oxide_api::types::IpRange {
+        match (self.first, self.last) {
+            (IpAddr::V4(first), IpAddr::V4(last)) => {
+                oxide_api::types::IpRange::V4(oxide_api::types::Ipv4Range { first, last })
+            }
+            (IpAddr::V6(first), IpAddr::V6(last)) => {
+                oxide_api::types::IpRange::V6(oxide_api::types::Ipv6Range { first, last })
+            }
+            // We never construct a range with mixed address families.
+            _ => unreachable!("mixed IPv4 and IPv6 range"),
+        }
+    }
+}
+
+impl From<&oxide_api::types::IpRange> for AddressRange {
+    fn from(range: &oxide_api::types::IpRange) -> Self {
+        match range {
+            oxide_api::types::IpRange::V4(r) => AddressRange {
+                first: IpAddr::V4(r.first),
+                last: IpAddr::V4(r.last),
+            },
+            oxide_api::types::IpRange::V6(r) => AddressRange {
+                first: IpAddr::V6(r.first),
+                last: IpAddr::V6(r.last),
+            },
+        }
+    }
+}
+
+impl FromStr for AddressRange {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Some((first, last)) = s.split_once('-') {
+            let first = IpAddr::from_str(first.trim()).map_err(|e| anyhow!("invalid address `{}`: {}", first, e))?;
+            let last = IpAddr::from_str(last.trim()).map_err(|e| anyhow!("invalid address `{}`: {}", last, e))?;
+
+            if first.is_ipv4() != last.is_ipv4() {
+                return Err(anyhow!("range `{}` mixes IPv4 and IPv6 addresses", s));
+            }
+
+            if first > last {
+                return Err(anyhow!(
+                    "the first address in range `{}` must not be greater than the last",
+                    s
+                ));
+            }
+
+            return Ok(AddressRange { first, last });
+        }
+
+        let network = ipnetwork::IpNetwork::from_str(s)
+            .map_err(|e| anyhow!("invalid CIDR or `first-last` range `{}`: {}", s, e))?;
+
+        Ok(AddressRange {
+            first: network.network(),
+            last: network.broadcast(),
+        })
+    }
+}
+
+impl std::fmt::Display for AddressRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.first, self.last)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{cmd::Command, cmd_ip_pool::AddressRange};
+
+    pub struct TestItem {
+        name: String,
+        cmd: crate::cmd_ip_pool::SubCommand,
+        stdin: String,
+        want_out: String,
+        want_err: String,
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_cmd_ip_pool() {
+        let tests: Vec<TestItem> = vec![
+            TestItem {
+                name: "create no name".to_string(),
+                cmd: crate::cmd_ip_pool::SubCommand::Create(crate::cmd_ip_pool::CmdIpPoolCreate {
+                    ip_pool: "".to_string(),
+                    description: "hi hi".to_string(),
+                    organization: "".to_string(),
+                    project: "".to_string(),
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "[ip_pool] required in non-interactive mode".to_string(),
+            },
+            TestItem {
+                name: "create no description".to_string(),
+                cmd: crate::cmd_ip_pool::SubCommand::Create(crate::cmd_ip_pool::CmdIpPoolCreate {
+                    ip_pool: "things".to_string(),
+                    description: "".to_string(),
+                    organization: "".to_string(),
+                    project: "".to_string(),
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "-D|--description required in non-interactive mode".to_string(),
+            },
+            TestItem {
+                name: "delete no --confirm non-interactive".to_string(),
+                cmd: crate::cmd_ip_pool::SubCommand::Delete(crate::cmd_ip_pool::CmdIpPoolDelete {
+                    ip_pool: "things".to_string(),
+                    confirm: false,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--confirm required when not running interactively".to_string(),
+            },
+            TestItem {
+                name: "list zero limit".to_string(),
+                cmd: crate::cmd_ip_pool::SubCommand::List(crate::cmd_ip_pool::CmdIpPoolList {
+                    sort_by: Default::default(),
+                    limit: 0,
+                    paginate: false,
+                    format: None,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--limit must be greater than 0".to_string(),
+            },
+            TestItem {
+                name: "range list zero limit".to_string(),
+                cmd: crate::cmd_ip_pool::SubCommand::Range(crate::cmd_ip_pool::CmdIpPoolRange {
+                    subcmd: crate::cmd_ip_pool::RangeSubCommand::List(crate::cmd_ip_pool::CmdIpPoolRangeList {
+                        ip_pool: "things".to_string(),
+                        limit: 0,
+                        paginate: false,
+                        format: None,
+                    }),
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--limit must be greater than 0".to_string(),
+            },
+            TestItem {
+                name: "range remove no --confirm non-interactive".to_string(),
+                cmd: crate::cmd_ip_pool::SubCommand::Range(crate::cmd_ip_pool::CmdIpPoolRange {
+                    subcmd: crate::cmd_ip_pool::RangeSubCommand::Remove(crate::cmd_ip_pool::CmdIpPoolRangeRemove {
+                        ip_pool: "things".to_string(),
+                        range: AddressRange::from_str("10.0.0.0/24").unwrap(),
+                        confirm: false,
+                    }),
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--confirm required when not running interactively".to_string(),
+            },
+        ];
+
+        let mut config = crate::config::new_blank_config().unwrap();
+        let mut c = crate::config_from_env::EnvConfig::inherit_env(&mut config);
+
+        for t in tests {
+            let (mut io, stdout_path, stderr_path) = crate::iostreams::IoStreams::test();
+            if !t.stdin.is_empty() {
+                io.stdin = Box::new(std::io::Cursor::new(t.stdin));
+            }
+            // We need to also turn off the fancy terminal colors.
+            // This ensures it also works in GitHub actions/any CI.
+            io.set_color_enabled(false);
+            io.set_never_prompt(true);
+            let mut ctx = crate::context::Context {
+                config: &mut c,
+                io,
+                debug: false,
+            };
+
+            let cmd_ip_pool = crate::cmd_ip_pool::CmdIpPool { subcmd: t.cmd };
+            match cmd_ip_pool.run(&mut ctx).await {
+                Ok(()) => {
+                    let stdout = std::fs::read_to_string(stdout_path).unwrap();
+                    let stderr = std::fs::read_to_string(stderr_path).unwrap();
+                    assert!(stderr.is_empty(), "test {}: {}", t.name, stderr);
+                    if !stdout.contains(&t.want_out) {
+                        assert_eq!(stdout, t.want_out, "test {}: stdout mismatch", t.name);
+                    }
+                }
+                Err(err) => {
+                    let stdout = std::fs::read_to_string(stdout_path).unwrap();
+                    let stderr = std::fs::read_to_string(stderr_path).unwrap();
+                    assert_eq!(stdout, t.want_out, "test {}", t.name);
+                    if !err.to_string().contains(&t.want_err) {
+                        assert_eq!(err.to_string(), t.want_err, "test {}: err mismatch", t.name);
+                    }
+                    assert!(stderr.is_empty(), "test {}: {}", t.name, stderr);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_address_range_parse() {
+        let range = AddressRange::from_str("10.0.0.0/24").unwrap();
+        assert_eq!(range.to_string(), "10.0.0.0-10.0.0.255");
+
+        let range = AddressRange::from_str("10.0.0.10-10.0.0.20").unwrap();
+        assert_eq!(range.to_string(), "10.0.0.10-10.0.0.20");
+
+        let range = AddressRange::from_str("fd00::/126").unwrap();
+        assert_eq!(range.to_string(), "fd00::-fd00::3");
+
+        assert!(AddressRange::from_str("10.0.0.20-10.0.0.10").is_err());
+        assert!(AddressRange::from_str("10.0.0.1-fd00::1").is_err());
+        assert!(AddressRange::from_str("not a range").is_err());
+    }
+
+    #[test]
+    fn test_address_range_overlaps() {
+        let a = AddressRange::from_str("10.0.0.0/24").unwrap();
+
+        assert!(a.overlaps(&AddressRange::from_str("10.0.0.255-10.0.1.10").unwrap()));
+        assert!(a.overlaps(&AddressRange::from_str("10.0.0.16/28").unwrap()));
+        assert!(!a.overlaps(&AddressRange::from_str("10.0.1.0/24").unwrap()));
+        assert!(!a.overlaps(&AddressRange::from_str("::/0").unwrap()));
+    }
+}
@@ -0,0 +1,294 @@
+use std::io::Write;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use parse_display::{Display, FromStr};
+
+/// Explore the metrics exported by the rack.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdMetrics {
+    #[clap(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser, Debug, Clone)]
+enum SubCommand {
+    Schema(CmdMetricsSchema),
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdMetrics {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        match &self.subcmd {
+            SubCommand::Schema(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+/// List timeseries schemas or view the schema of a timeseries.
+///
+/// Without a timeseries name, all the timeseries the rack exports are listed with
+/// their datum type and fields. Use `--filter` to only list the timeseries whose
+/// name starts with a prefix, like `--filter crucible_upstairs:`.
+///
+/// With a timeseries name, the fields of that timeseries are shown. Pass
+/// `--format json-schema` to get a JSON schema of its measurements instead, for
+/// generating code.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdMetricsSchema {
+    /// The name of the timeseries to view.
+    #[clap(name = "timeseries")]
+    pub timeseries: Option<String>,
+
+    /// Only list the timeseries whose name starts with this prefix.
+    #[clap(long, conflicts_with = "timeseries")]
+    pub filter: Option<String>,
+
+    /// Display output in json, yaml, table, or json-schema format.
+    #[clap(long, short)]
+    pub format: Option<SchemaFormat>,
+}
+
+/// The formats a timeseries schema can be displayed in.
+#[derive(PartialEq, Eq, Debug, Clone, FromStr, Display)]
+#[display(style = "kebab-case")]
+pub enum SchemaFormat {
+    Json,
+    Yaml,
+    Table,
+    /// A JSON schema of the measurements of the timeseries.
+    JsonSchema,
+}
+
+/// A timeseries in the list of schemas.
+#[derive(Debug, Clone, serde::Serialize, tabled::Tabled)]
+struct TimeseriesSummary {
+    timeseries_name: String,
+    datum_type: String,
+    fields: String,
+}
+
+/// A field of a timeseries.
+#[derive(Debug, Clone, serde::Serialize, tabled::Tabled)]
+struct TimeseriesField {
+    name: String,
+    ty: String,
+    source: String,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdMetricsSchema {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if self.timeseries.is_none() && self.format == Some(SchemaFormat::JsonSchema) {
+            return Err(anyhow!("--format json-schema requires a timeseries"));
+        }
+
+        let format = match &self.format {
+            Some(SchemaFormat::Json) => crate::types::FormatOutput::Json,
+            Some(SchemaFormat::Yaml) => crate::types::FormatOutput::Yaml,
+            Some(SchemaFormat::Table) => crate::types::FormatOutput::Table,
+            Some(SchemaFormat::JsonSchema) | None => ctx.format(&None)?,
+        };
+
+        let client = ctx.api_client("")?;
+
+        // There is no endpoint to get a single schema, so we always fetch them all.
+        let mut schemas = client.metrics().timeseries_schema_get_all().await?;
+        schemas.sort_by_key(|s| s.timeseries_name.to_string());
+
+        let name = match &self.timeseries {
+            Some(name) => name,
+            None => {
+                let prefix = self.filter.as_deref().unwrap_or_default();
+                schemas.retain(|s| s.timeseries_name.to_string().starts_with(prefix));
+
+                if format != crate::types::FormatOutput::Table {
+                    ctx.io.write_output_for_vec(&format, &schemas)?;
+                    return Ok(());
+                }
+
+                let summaries: Vec<TimeseriesSummary> = schemas
+                    .iter()
+                    .map(|s| TimeseriesSummary {
+                        timeseries_name: s.timeseries_name.to_string(),
+                        datum_type: s.datum_type.to_string(),
+                        fields: s
+                            .field_schema
+                            .iter()
+                            .map(|f| format!("{}:{}", f.name, f.ty))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    })
+                    .collect();
+                ctx.io.write_output_for_vec(&format, &summaries)?;
+                return Ok(());
+            }
+        };
+
+        let schema = schemas
+            .into_iter()
+            .find(|s| s.timeseries_name.to_string() == *name)
+            .ok_or_else(|| anyhow!("timeseries {} not found", name))?;
+
+        if self.format == Some(SchemaFormat::JsonSchema) {
+            let fields: Vec<(String, String)> = schema
+                .field_schema
+                .iter()
+                .map(|f| (f.name.to_string(), f.ty.to_string()))
+                .collect();
+            let json_schema = measurement_json_schema(name, &schema.datum_type.to_string(), &fields);
+            ctx.io.write_output_json(&json_schema)?;
+            return Ok(());
+        }
+
+        if format != crate::types::FormatOutput::Table {
+            ctx.io.write_output(&format, &schema)?;
+            return Ok(());
+        }
+
+        let fields: Vec<TimeseriesField> = schema
+            .field_schema
+            .iter()
+            .map(|f| TimeseriesField {
+                name: f.name.to_string(),
+                ty: f.ty.to_string(),
+                source: f.source.to_string(),
+            })
+            .collect();
+        writeln!(ctx.io.out, "Datum type: {}", schema.datum_type)?;
+        ctx.io.write_output_for_vec(&format, &fields)?;
+
+        Ok(())
+    }
+}
+
+/// Returns a JSON schema for a measurement of the timeseries: its fields, when it
+/// was taken, and its datum.
+fn measurement_json_schema(name: &str, datum_type: &str, fields: &[(String, String)]) -> serde_json::Value {
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+
+    for (field, ty) in fields {
+        properties.insert(field.to_string(), field_json_schema(ty));
+        required.push(field.to_string());
+    }
+
+    properties.insert(
+        "timestamp".to_string(),
+        serde_json::json!({"type": "string", "format": "date-time"}),
+    );
+    properties.insert("datum".to_string(), datum_json_schema(datum_type));
+    required.push("timestamp".to_string());
+    required.push("datum".to_string());
+
+    serde_json::json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": name,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// Returns the JSON schema of a field of the given type.
+fn field_json_schema(ty: &str) -> serde_json::Value {
+    match ty {
+        "i64" => serde_json::json!({"type": "integer", "format": "int64"}),
+        "bool" => serde_json::json!({"type": "boolean"}),
+        "uuid" => serde_json::json!({"type": "string", "format": "uuid"}),
+        "ip_addr" => serde_json::json!({"type": "string", "format": "ip"}),
+        _ => serde_json::json!({"type": "string"}),
+    }
+}
+
+/// Returns the JSON schema of a datum of the given type.
+fn datum_json_schema(datum_type: &str) -> serde_json::Value {
+    let bin = |ty: &str, format: &str| {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "range": {
+                    "type": "object",
+                    "properties": {
+                        "start": {"type": ty, "format": format},
+                        "end": {"type": ty, "format": format},
+                    },
+                },
+                "count": {"type": "integer", "format": "uint64"},
+            },
+            "required": ["range", "count"],
+        })
+    };
+
+    match datum_type {
+        "bool" => serde_json::json!({"type": "boolean"}),
+        "i64" => serde_json::json!({"type": "integer", "format": "int64"}),
+        "f64" => serde_json::json!({"type": "number", "format": "double"}),
+        "bytes" => serde_json::json!({"type": "array", "items": {"type": "integer", "format": "uint8"}}),
+        "cumulative_i64" | "cumulative_f64" => {
+            let value = if datum_type == "cumulative_i64" {
+                serde_json::json!({"type": "integer", "format": "int64"})
+            } else {
+                serde_json::json!({"type": "number", "format": "double"})
+            };
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "start_time": {"type": "string", "format": "date-time"},
+                    "value": value,
+                },
+                "required": ["start_time", "value"],
+            })
+        }
+        "histogram_i64" | "histogram_f64" => {
+            let bin = if datum_type == "histogram_i64" {
+                bin("integer", "int64")
+            } else {
+                bin("number", "double")
+            };
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "start_time": {"type": "string", "format": "date-time"},
+                    "bins": {"type": "array", "items": bin},
+                    "n_samples": {"type": "integer", "format": "uint64"},
+                },
+                "required": ["start_time", "bins", "n_samples"],
+            })
+        }
+        _ => serde_json::json!({"type": "string"}),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_measurement_json_schema() {
+        let schema = crate::cmd_metrics::measurement_json_schema(
+            "crucible_upstairs:write",
+            "cumulative_i64",
+            &[
+                ("upstairs_uuid".to_string(), "uuid".to_string()),
+                ("count".to_string(), "i64".to_string()),
+            ],
+        );
+
+        assert_eq!(schema["title"], "crucible_upstairs:write");
+        assert_eq!(
+            schema["required"],
+            serde_json::json!(["upstairs_uuid", "count", "timestamp", "datum"])
+        );
+        assert_eq!(
+            schema["properties"]["upstairs_uuid"],
+            serde_json::json!({"type": "string", "format": "uuid"})
+        );
+        assert_eq!(
+            schema["properties"]["datum"]["properties"]["value"],
+            serde_json::json!({"type": "integer", "format": "int64"})
+        );
+    }
+}
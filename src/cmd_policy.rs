@@ -0,0 +1,600 @@
+use std::io::Write;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+/// The number of times to try to update a policy that is being changed concurrently.
+const MAX_POLICY_UPDATE_ATTEMPTS: u32 = 3;
+
+/// View and edit IAM policies.
+///
+/// A policy is the list of roles granted directly on a resource to users and groups.
+/// Policies can be managed for the fleet (`--global`), a silo (`--silo`), an
+/// organization (`--organization`), or a project (`--organization` and `--project`).
+/// Without any of those, the policy of the current silo is used.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdPolicy {
+    #[clap(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser, Debug, Clone)]
+enum SubCommand {
+    Add(CmdPolicyAdd),
+    Remove(CmdPolicyRemove),
+    View(CmdPolicyView),
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdPolicy {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        match &self.subcmd {
+            SubCommand::Add(cmd) => cmd.run(ctx).await,
+            SubCommand::Remove(cmd) => cmd.run(ctx).await,
+            SubCommand::View(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+/// The flags selecting which policy to operate on.
+#[derive(Parser, Debug, Clone, Default)]
+pub struct PolicyScopeArgs {
+    /// Use the policy of the fleet.
+    #[clap(long, conflicts_with_all = &["silo", "organization", "project"])]
+    pub global: bool,
+
+    /// Use the policy of the silo.
+    #[clap(long, short, conflicts_with_all = &["organization", "project"])]
+    pub silo: Option<String>,
+
+    /// Use the policy of the organization, or the organization that holds the project.
+    #[clap(long, short, alias = "org")]
+    pub organization: Option<String>,
+
+    /// Use the policy of the project.
+    #[clap(long, short, requires = "organization")]
+    pub project: Option<String>,
+}
+
+/// View an IAM policy.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdPolicyView {
+    #[clap(flatten)]
+    pub scope: PolicyScopeArgs,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdPolicyView {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let scope = PolicyScope::from(&self.scope);
+
+        let client = ctx.api_client("")?;
+
+        let policy = scope.get(&client).await?;
+
+        let format = ctx.format(&self.format)?;
+        ctx.io.write_output_for_vec(&format, &policy.role_assignments)?;
+        Ok(())
+    }
+}
+
+/// Grant a role to a user or group.
+///
+/// The role is checked against the built-in roles for the kind of resource the policy
+/// is for, see `oxide role list`. It can be given either as `viewer` or in full as
+/// `project.viewer`.
+///
+/// The change to the policy is shown and has to be confirmed before it is made. If
+/// the policy was changed by someone else while it was being updated, the change is
+/// applied again to the new policy.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdPolicyAdd {
+    /// The ID of the user or group to grant the role to.
+    #[clap(name = "identity", required = true)]
+    pub identity: String,
+
+    /// The role to grant.
+    #[clap(name = "role", required = true)]
+    pub role: String,
+
+    /// The identity is a group rather than a user.
+    #[clap(long, short)]
+    pub group: bool,
+
+    #[clap(flatten)]
+    pub scope: PolicyScopeArgs,
+
+    /// Confirm the change without prompting.
+    #[clap(long)]
+    pub confirm: bool,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdPolicyAdd {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let scope = PolicyScope::from(&self.scope);
+        let assignment = RoleAssignment::new(&self.identity, self.group)?;
+
+        update_policy(ctx, &scope, PolicyChange::Add, assignment, &self.role, self.confirm).await
+    }
+}
+
+/// Revoke a role from a user or group.
+///
+/// The change to the policy is shown and has to be confirmed before it is made. If
+/// the policy was changed by someone else while it was being updated, the change is
+/// applied again to the new policy.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdPolicyRemove {
+    /// The ID of the user or group to revoke the role from.
+    #[clap(name = "identity", required = true)]
+    pub identity: String,
+
+    /// The role to revoke.
+    #[clap(name = "role", required = true)]
+    pub role: String,
+
+    /// The identity is a group rather than a user.
+    #[clap(long, short)]
+    pub group: bool,
+
+    #[clap(flatten)]
+    pub scope: PolicyScopeArgs,
+
+    /// Confirm the change without prompting.
+    #[clap(long)]
+    pub confirm: bool,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdPolicyRemove {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let scope = PolicyScope::from(&self.scope);
+        let assignment = RoleAssignment::new(&self.identity, self.group)?;
+
+        update_policy(ctx, &scope, PolicyChange::Remove, assignment, &self.role, self.confirm).await
+    }
+}
+
+/// Apply a single role assignment change to the policy and write it back, once the
+/// change has been shown and confirmed.
+async fn update_policy(
+    ctx: &mut crate::context::Context<'_>,
+    scope: &PolicyScope,
+    change: PolicyChange,
+    mut assignment: RoleAssignment,
+    role: &str,
+    confirm: bool,
+) -> Result<()> {
+    if !ctx.io.can_prompt() && !confirm {
+        return Err(anyhow!("--confirm required when not running interactively"));
+    }
+
+    let client = ctx.api_client("")?;
+
+    assignment.role_name = scope.validate_role(&client, role).await?;
+
+    let mut confirmed = confirm;
+    let mut attempt = 1;
+    loop {
+        let current = scope.get(&client).await?;
+
+        let updated = match change.apply(&current, &assignment)? {
+            Some(updated) => updated,
+            None => {
+                writeln!(
+                    ctx.io.out,
+                    "Identity {} already has role {} on {}, nothing to change",
+                    assignment.identity_id, assignment.role_name, scope
+                )?;
+                return Ok(());
+            }
+        };
+
+        // Show the change before making it. The change is the same on each attempt, so
+        // it only needs confirming once.
+        write_policy_diff(ctx, &current, &updated)?;
+        if !confirmed {
+            match dialoguer::Confirm::new()
+                .with_prompt(format!("Apply this change to the policy of {}?", scope))
+                .interact()
+            {
+                Ok(true) => confirmed = true,
+                Ok(false) => {
+                    writeln!(ctx.io.out, "Policy of {} not changed", scope)?;
+                    return Ok(());
+                }
+                Err(err) => {
+                    return Err(anyhow!("prompt failed: {}", err));
+                }
+            }
+        }
+
+        // The API doesn't reject an update made on top of a stale policy, so check the
+        // policy wasn't changed by someone else since we fetched it, and start over from
+        // the new policy if it was. A change made between this check and the update is
+        // still lost.
+        if scope.get(&client).await? != current {
+            if attempt >= MAX_POLICY_UPDATE_ATTEMPTS {
+                return Err(anyhow!(
+                    "the policy of {} kept changing while it was being updated, try again",
+                    scope
+                ));
+            }
+
+            writeln!(
+                ctx.io.out,
+                "The policy of {} changed while it was being updated, applying the change again",
+                scope
+            )?;
+            attempt += 1;
+            continue;
+        }
+
+        scope.put(&client, &updated).await?;
+
+        let cs = ctx.io.color_scheme();
+        match change {
+            PolicyChange::Add => writeln!(
+                ctx.io.out,
+                "{} Granted role {} on {} to {}",
+                cs.success_icon(),
+                assignment.role_name,
+                scope,
+                assignment.identity_id
+            )?,
+            PolicyChange::Remove => writeln!(
+                ctx.io.out,
+                "{} Revoked role {} on {} from {}",
+                cs.success_icon_with_color(ansi_term::Color::Red),
+                assignment.role_name,
+                scope,
+                assignment.identity_id
+            )?,
+        }
+
+        return Ok(());
+    }
+}
+
+/// Print the role assignments of the policy before and after the change, marking the
+/// ones that were added and removed.
+fn write_policy_diff(ctx: &mut crate::context::Context, before: &Policy, after: &Policy) -> Result<()> {
+    let mut all: Vec<&RoleAssignment> = before
+        .role_assignments
+        .iter()
+        .chain(after.role_assignments.iter())
+        .collect();
+    all.sort();
+    all.dedup();
+
+    let cs = ctx.io.color_scheme();
+    for assignment in all {
+        let line = format!(
+            "{} {} {}",
+            assignment.role_name, assignment.identity_type, assignment.identity_id
+        );
+        match (
+            before.role_assignments.contains(assignment),
+            after.role_assignments.contains(assignment),
+        ) {
+            (true, false) => writeln!(ctx.io.out, "{}", cs.red(&format!("- {}", line)))?,
+            (false, true) => writeln!(ctx.io.out, "{}", cs.green(&format!("+ {}", line)))?,
+            _ => writeln!(ctx.io.out, "  {}", line)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// The resource a policy is attached to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyScope {
+    Global,
+    /// The silo, or the current silo if none was given.
+    Silo(Option<String>),
+    Organization(String),
+    Project {
+        organization: String,
+        project: String,
+    },
+}
+
+impl From<&PolicyScopeArgs> for PolicyScope {
+    fn from(args: &PolicyScopeArgs) -> Self {
+        if args.global {
+            return PolicyScope::Global;
+        }
+
+        match (&args.organization, &args.project) {
+            (Some(organization), Some(project)) => PolicyScope::Project {
+                organization: organization.to_string(),
+                project: project.to_string(),
+            },
+            (Some(organization), None) => PolicyScope::Organization(organization.to_string()),
+            _ => PolicyScope::Silo(args.silo.clone()),
+        }
+    }
+}
+
+impl std::fmt::Display for PolicyScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyScope::Global => write!(f, "the fleet"),
+            PolicyScope::Silo(Some(silo)) => write!(f, "silo {}", silo),
+            PolicyScope::Silo(None) => write!(f, "the current silo"),
+            PolicyScope::Organization(organization) => write!(f, "organization {}", organization),
+            PolicyScope::Project { organization, project } => write!(f, "project {}/{}", organization, project),
+        }
+    }
+}
+
+impl PolicyScope {
+    /// The prefix of the built-in role names that apply to this kind of resource.
+    pub fn role_prefix(&self) -> &str {
+        match self {
+            PolicyScope::Global => "fleet",
+            PolicyScope::Silo(_) => "silo",
+            PolicyScope::Organization(_) => "organization",
+            PolicyScope::Project { .. } => "project",
+        }
+    }
+
+    /// Make sure the role is a built-in role for this kind of resource and return its
+    /// short name, as used in policies.
+    async fn validate_role(&self, client: &oxide_api::Client, role: &str) -> Result<String> {
+        let prefix = format!("{}.", self.role_prefix());
+        let role = role.strip_prefix(&prefix).unwrap_or(role);
+
+        let valid: Vec<String> = client
+            .roles()
+            .get_all()
+            .await?
+            .iter()
+            .filter_map(|r| r.name.to_string().strip_prefix(&prefix).map(|n| n.to_string()))
+            .collect();
+
+        if !valid.iter().any(|v| v == role) {
+            return Err(anyhow!(
+                "invalid role {} for {}, valid roles are: {}",
+                role,
+                self,
+                valid.join(", ")
+            ));
+        }
+
+        Ok(role.to_string())
+    }
+
+    /// Fetch the policy.
+    pub async fn get(&self, client: &oxide_api::Client) -> Result<Policy> {
+        // The policies of the different kinds of resources only differ in the type of
+        // their role names, so we go through JSON rather than handle each one.
+        let value = match self {
+            PolicyScope::Global => serde_json::to_value(client.policy().global_view().await?)?,
+            PolicyScope::Silo(Some(silo)) => serde_json::to_value(client.silos().get_silo_policy(silo).await?)?,
+            PolicyScope::Silo(None) => serde_json::to_value(client.silos().policy_get().await?)?,
+            PolicyScope::Organization(organization) => {
+                serde_json::to_value(client.organizations().get_policy(organization).await?)?
+            }
+            PolicyScope::Project { organization, project } => {
+                serde_json::to_value(client.projects().get_project_policy(organization, project).await?)?
+            }
+        };
+
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Replace the policy.
+    async fn put(&self, client: &oxide_api::Client, policy: &Policy) -> Result<()> {
+        let value = serde_json::to_value(policy)?;
+
+        match self {
+            PolicyScope::Global => {
+                client.policy().global_update(&serde_json::from_value(value)?).await?;
+            }
+            PolicyScope::Silo(Some(silo)) => {
+                client
+                    .silos()
+                    .put_silo_policy(silo, &serde_json::from_value(value)?)
+                    .await?;
+            }
+            PolicyScope::Silo(None) => {
+                client.silos().policy_put(&serde_json::from_value(value)?).await?;
+            }
+            PolicyScope::Organization(organization) => {
+                client
+                    .organizations()
+                    .put_policy(organization, &serde_json::from_value(value)?)
+                    .await?;
+            }
+            PolicyScope::Project { organization, project } => {
+                client
+                    .projects()
+                    .put_project_policy(organization, project, &serde_json::from_value(value)?)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A policy of any kind of resource.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Policy {
+    pub role_assignments: Vec<RoleAssignment>,
+}
+
+/// A role granted to a user or group.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, tabled::Tabled)]
+pub struct RoleAssignment {
+    pub role_name: String,
+    pub identity_type: String,
+    pub identity_id: String,
+}
+
+impl RoleAssignment {
+    fn new(identity: &str, group: bool) -> Result<Self> {
+        if uuid::Uuid::parse_str(identity).is_err() {
+            return Err(anyhow!("identity must be the ID of a user or group, got {}", identity));
+        }
+
+        Ok(RoleAssignment {
+            role_name: String::new(),
+            identity_type: if group { "silo_group" } else { "silo_user" }.to_string(),
+            identity_id: identity.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PolicyChange {
+    Add,
+    Remove,
+}
+
+impl PolicyChange {
+    /// Return the policy with the change applied, or None if there is nothing to change.
+    fn apply(&self, policy: &Policy, assignment: &RoleAssignment) -> Result<Option<Policy>> {
+        let exists = policy.role_assignments.contains(assignment);
+
+        let mut updated = policy.clone();
+        match self {
+            PolicyChange::Add => {
+                if exists {
+                    return Ok(None);
+                }
+                updated.role_assignments.push(assignment.clone());
+            }
+            PolicyChange::Remove => {
+                if !exists {
+                    return Err(anyhow!(
+                        "identity {} does not have role {}",
+                        assignment.identity_id,
+                        assignment.role_name
+                    ));
+                }
+                updated.role_assignments.retain(|a| a != assignment);
+            }
+        }
+
+        Ok(Some(updated))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::cmd_policy::{Policy, PolicyChange, PolicyScope, PolicyScopeArgs, RoleAssignment};
+
+    fn assignment(role: &str, id: &str) -> RoleAssignment {
+        RoleAssignment {
+            role_name: role.to_string(),
+            identity_type: "silo_user".to_string(),
+            identity_id: id.to_string(),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_cmd_policy_add_requires_confirm() {
+        let mut config = crate::config::new_blank_config().unwrap();
+        let mut c = crate::config_from_env::EnvConfig::inherit_env(&mut config);
+
+        let (mut io, stdout_path, _stderr_path) = crate::iostreams::IoStreams::test();
+        io.set_color_enabled(false);
+        io.set_never_prompt(true);
+        let mut ctx = crate::context::Context {
+            config: &mut c,
+            io,
+            debug: false,
+        };
+
+        let cmd = crate::cmd_policy::CmdPolicyAdd {
+            identity: "8b5c3e13-5a6d-4b5f-9c2b-0e9a4a6b1c11".to_string(),
+            role: "viewer".to_string(),
+            group: false,
+            scope: PolicyScopeArgs::default(),
+            confirm: false,
+        };
+
+        let err = crate::cmd::Command::run(&cmd, &mut ctx).await.unwrap_err();
+        assert_eq!(err.to_string(), "--confirm required when not running interactively");
+        assert!(std::fs::read_to_string(stdout_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_policy_change_apply() {
+        let alice = assignment("viewer", "8b5c3e13-5a6d-4b5f-9c2b-0e9a4a6b1c11");
+        let bob = assignment("admin", "e6d1b4b2-7a3c-4b8e-8d0f-5b2a9c6e3d22");
+        let policy = Policy {
+            role_assignments: vec![alice.clone()],
+        };
+
+        // Adding a new assignment appends it.
+        let updated = PolicyChange::Add.apply(&policy, &bob).unwrap().unwrap();
+        assert_eq!(updated.role_assignments, vec![alice.clone(), bob.clone()]);
+
+        // Adding an existing assignment is a no-op.
+        assert_eq!(PolicyChange::Add.apply(&policy, &alice).unwrap(), None);
+
+        // Removing only touches the matching assignment.
+        let updated = PolicyChange::Remove.apply(&updated, &alice).unwrap().unwrap();
+        assert_eq!(updated.role_assignments, vec![bob.clone()]);
+
+        // Removing an assignment that is not there is an error.
+        let err = PolicyChange::Remove.apply(&policy, &bob).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "identity e6d1b4b2-7a3c-4b8e-8d0f-5b2a9c6e3d22 does not have role admin"
+        );
+    }
+
+    #[test]
+    fn test_policy_scope_from_args() {
+        assert_eq!(PolicyScope::from(&PolicyScopeArgs::default()), PolicyScope::Silo(None));
+        assert_eq!(
+            PolicyScope::from(&PolicyScopeArgs {
+                global: true,
+                ..Default::default()
+            }),
+            PolicyScope::Global
+        );
+        assert_eq!(
+            PolicyScope::from(&PolicyScopeArgs {
+                organization: Some("maze-war".to_string()),
+                ..Default::default()
+            }),
+            PolicyScope::Organization("maze-war".to_string())
+        );
+        assert_eq!(
+            PolicyScope::from(&PolicyScopeArgs {
+                organization: Some("maze-war".to_string()),
+                project: Some("prod".to_string()),
+                ..Default::default()
+            }),
+            PolicyScope::Project {
+                organization: "maze-war".to_string(),
+                project: "prod".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_role_assignment_new() {
+        let a = RoleAssignment::new("8b5c3e13-5a6d-4b5f-9c2b-0e9a4a6b1c11", true).unwrap();
+        assert_eq!(a.identity_type, "silo_group");
+
+        assert!(RoleAssignment::new("alice", false).is_err());
+    }
+}
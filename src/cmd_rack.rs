@@ -3,6 +3,8 @@ use clap::Parser;
 use cli_macro::crud_gen;
 
 /// Manage racks.
+///
+/// Use `oxide rack ip-pool` to manage the IP pool used by the services running on a rack.
 #[derive(Parser, Debug, Clone)]
 #[clap(verbatim_doc_comment)]
 pub struct CmdRack {
@@ -14,12 +16,15 @@ pub struct CmdRack {
     tag = "racks",
 }]
 #[derive(Parser, Debug, Clone)]
-enum SubCommand {}
+enum SubCommand {
+    IpPool(crate::cmd_rack_ip_pool::CmdRackIpPool),
+}
 
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdRack {
     async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
         match &self.subcmd {
+            SubCommand::IpPool(cmd) => cmd.run(ctx).await,
             SubCommand::List(cmd) => cmd.run(ctx).await,
             SubCommand::View(cmd) => cmd.run(ctx).await,
         }
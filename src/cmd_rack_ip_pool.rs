@@ -0,0 +1,405 @@
+use std::io::Write;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+
+use crate::cmd_ip_pool::AddressRange;
+
+/// View the service IP pool of a rack and manage its address ranges.
+///
+/// The service IP pool holds the addresses used by the services running on the rack
+/// itself, rather than by instances.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdRackIpPool {
+    #[clap(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser, Debug, Clone)]
+enum SubCommand {
+    #[clap(alias = "ranges")]
+    Range(CmdRackIpPoolRange),
+    #[clap(alias = "get")]
+    View(CmdRackIpPoolView),
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdRackIpPool {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        match &self.subcmd {
+            SubCommand::Range(cmd) => cmd.run(ctx).await,
+            SubCommand::View(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+/// View the service IP pool of a rack.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdRackIpPoolView {
+    /// The ID of the rack. If not specified, you will be prompted to select one.
+    #[clap(long, short)]
+    pub rack: Option<String>,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdRackIpPoolView {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let rack = select_rack(ctx, &self.rack).await?;
+
+        let client = ctx.api_client("")?;
+
+        let result = client.ip_pools().service_view(&rack).await?;
+
+        let format = ctx.format(&self.format)?;
+        ctx.io.write_output(&format, &result)?;
+        Ok(())
+    }
+}
+
+/// List, add, and remove the address ranges of a rack's service IP pool.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdRackIpPoolRange {
+    #[clap(subcommand)]
+    subcmd: RangeSubCommand,
+}
+
+#[derive(Parser, Debug, Clone)]
+enum RangeSubCommand {
+    Add(CmdRackIpPoolRangeAdd),
+    List(CmdRackIpPoolRangeList),
+    Remove(CmdRackIpPoolRangeRemove),
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdRackIpPoolRange {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        match &self.subcmd {
+            RangeSubCommand::Add(cmd) => cmd.run(ctx).await,
+            RangeSubCommand::List(cmd) => cmd.run(ctx).await,
+            RangeSubCommand::Remove(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+/// Add an address range to a rack's service IP pool.
+///
+/// The range can be given as a CIDR block, like `10.0.0.0/24`, or as an inclusive
+/// `first-last` pair of addresses, like `10.0.0.10-10.0.0.20`.
+///
+/// Ranges that overlap a range already in the pool are refused.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdRackIpPoolRangeAdd {
+    /// The range of addresses to add.
+    #[clap(name = "range", required = true)]
+    pub range: AddressRange,
+
+    /// The ID of the rack. If not specified, you will be prompted to select one.
+    #[clap(long, short)]
+    pub rack: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdRackIpPoolRangeAdd {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let rack = select_rack(ctx, &self.rack).await?;
+
+        let client = ctx.api_client("")?;
+
+        let existing = client.ip_pools().service_range_list_all(&rack).await?;
+        self.range
+            .ensure_no_overlap(&existing, &format!("the service IP pool of rack {}", rack))?;
+
+        client
+            .ip_pools()
+            .service_range_add(&rack, &self.range.to_ip_range())
+            .await?;
+
+        let cs = ctx.io.color_scheme();
+        writeln!(
+            ctx.io.out,
+            "{} Added range {} to the service IP pool of rack {}",
+            cs.success_icon(),
+            self.range,
+            rack
+        )?;
+
+        Ok(())
+    }
+}
+
+/// List the address ranges of a rack's service IP pool.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdRackIpPoolRangeList {
+    /// The ID of the rack. If not specified, you will be prompted to select one.
+    #[clap(long, short)]
+    pub rack: Option<String>,
+
+    /// Maximum number of ranges to list.
+    #[clap(long, short, default_value = "30")]
+    pub limit: u32,
+
+    /// Make additional HTTP requests to fetch all pages.
+    #[clap(long)]
+    pub paginate: bool,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdRackIpPoolRangeList {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if self.limit < 1 {
+            return Err(anyhow!("--limit must be greater than 0"));
+        }
+
+        let rack = select_rack(ctx, &self.rack).await?;
+
+        let client = ctx.api_client("")?;
+
+        let results = if self.paginate {
+            client.ip_pools().service_range_list_all(&rack).await?
+        } else {
+            client.ip_pools().service_range_list(self.limit, "", &rack).await?
+        };
+
+        let format = ctx.format(&self.format)?;
+        ctx.io.write_output_for_vec(&format, &results)?;
+        Ok(())
+    }
+}
+
+/// Remove an address range from a rack's service IP pool.
+///
+/// The range must match a range in the pool exactly. It can be given as a CIDR
+/// block or as an inclusive `first-last` pair of addresses.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdRackIpPoolRangeRemove {
+    /// The range of addresses to remove.
+    #[clap(name = "range", required = true)]
+    pub range: AddressRange,
+
+    /// The ID of the rack. If not specified, you will be prompted to select one.
+    #[clap(long, short)]
+    pub rack: Option<String>,
+
+    /// Confirm removal without prompting.
+    #[clap(long)]
+    pub confirm: bool,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdRackIpPoolRangeRemove {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if !ctx.io.can_prompt() && !self.confirm {
+            return Err(anyhow!("--confirm required when not running interactively"));
+        }
+
+        let rack = select_rack(ctx, &self.rack).await?;
+
+        let client = ctx.api_client("")?;
+
+        // Confirm removal.
+        if !self.confirm {
+            let range = self.range.to_string();
+            if let Err(err) = dialoguer::Input::<String>::new()
+                .with_prompt(format!("Type {} to confirm removal:", range))
+                .validate_with(|input: &String| -> Result<(), &str> {
+                    if input.trim() == range {
+                        Ok(())
+                    } else {
+                        Err("mismatched confirmation")
+                    }
+                })
+                .interact_text()
+            {
+                return Err(anyhow!("prompt failed: {}", err));
+            }
+        }
+
+        client
+            .ip_pools()
+            .service_range_remove(&rack, &self.range.to_ip_range())
+            .await?;
+
+        let cs = ctx.io.color_scheme();
+        writeln!(
+            ctx.io.out,
+            "{} Removed range {} from the service IP pool of rack {}",
+            cs.success_icon_with_color(ansi_term::Color::Red),
+            self.range,
+            rack
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Return the rack that was passed in, or prompt the user to select one of the racks
+/// in the system.
+async fn select_rack(ctx: &mut crate::context::Context<'_>, rack: &Option<String>) -> Result<String> {
+    if let Some(rack) = rack {
+        return Ok(rack.to_string());
+    }
+
+    if !ctx.io.can_prompt() {
+        return Err(anyhow!("-r|--rack required in non-interactive mode"));
+    }
+
+    let client = ctx.api_client("")?;
+
+    let racks: Vec<String> = client
+        .racks()
+        .get_all(oxide_api::types::IdSortMode::IdAscending)
+        .await?
+        .into_iter()
+        .map(|rack| rack.id)
+        .collect();
+
+    match racks.len() {
+        0 => Err(anyhow!("no racks found")),
+        // There is nothing to choose from.
+        1 => Ok(racks[0].to_string()),
+        _ => match dialoguer::Select::new()
+            .with_prompt("Select rack:")
+            .items(&racks)
+            .interact()
+        {
+            Ok(index) => Ok(racks[index].to_string()),
+            Err(err) => Err(anyhow!("prompt failed: {}", err)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{cmd::Command, cmd_ip_pool::AddressRange};
+
+    pub struct TestItem {
+        name: String,
+        cmd: crate::cmd_rack_ip_pool::SubCommand,
+        stdin: String,
+        want_out: String,
+        want_err: String,
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_cmd_rack_ip_pool() {
+        let tests: Vec<TestItem> = vec![
+            TestItem {
+                name: "view no rack non-interactive".to_string(),
+                cmd: crate::cmd_rack_ip_pool::SubCommand::View(crate::cmd_rack_ip_pool::CmdRackIpPoolView {
+                    rack: None,
+                    format: None,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "-r|--rack required in non-interactive mode".to_string(),
+            },
+            TestItem {
+                name: "range list zero limit".to_string(),
+                cmd: crate::cmd_rack_ip_pool::SubCommand::Range(crate::cmd_rack_ip_pool::CmdRackIpPoolRange {
+                    subcmd: crate::cmd_rack_ip_pool::RangeSubCommand::List(
+                        crate::cmd_rack_ip_pool::CmdRackIpPoolRangeList {
+                            rack: None,
+                            limit: 0,
+                            paginate: false,
+                            format: None,
+                        },
+                    ),
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--limit must be greater than 0".to_string(),
+            },
+            TestItem {
+                name: "range add no rack non-interactive".to_string(),
+                cmd: crate::cmd_rack_ip_pool::SubCommand::Range(crate::cmd_rack_ip_pool::CmdRackIpPoolRange {
+                    subcmd: crate::cmd_rack_ip_pool::RangeSubCommand::Add(
+                        crate::cmd_rack_ip_pool::CmdRackIpPoolRangeAdd {
+                            range: AddressRange::from_str("172.20.0.0/28").unwrap(),
+                            rack: None,
+                        },
+                    ),
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "-r|--rack required in non-interactive mode".to_string(),
+            },
+            TestItem {
+                name: "range remove no --confirm non-interactive".to_string(),
+                cmd: crate::cmd_rack_ip_pool::SubCommand::Range(crate::cmd_rack_ip_pool::CmdRackIpPoolRange {
+                    subcmd: crate::cmd_rack_ip_pool::RangeSubCommand::Remove(
+                        crate::cmd_rack_ip_pool::CmdRackIpPoolRangeRemove {
+                            range: AddressRange::from_str("172.20.0.0/28").unwrap(),
+                            rack: None,
+                            confirm: false,
+                        },
+                    ),
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--confirm required when not running interactively".to_string(),
+            },
+        ];
+
+        let mut config = crate::config::new_blank_config().unwrap();
+        let mut c = crate::config_from_env::EnvConfig::inherit_env(&mut config);
+
+        for t in tests {
+            let (mut io, stdout_path, stderr_path) = crate::iostreams::IoStreams::test();
+            if !t.stdin.is_empty() {
+                io.stdin = Box::new(std::io::Cursor::new(t.stdin));
+            }
+            // We need to also turn off the fancy terminal colors.
+            // This ensures it also works in GitHub actions/any CI.
+            io.set_color_enabled(false);
+            io.set_never_prompt(true);
+            let mut ctx = crate::context::Context {
+                config: &mut c,
+                io,
+                debug: false,
+            };
+
+            let cmd_rack_ip_pool = crate::cmd_rack_ip_pool::CmdRackIpPool { subcmd: t.cmd };
+            match cmd_rack_ip_pool.run(&mut ctx).await {
+                Ok(()) => {
+                    let stdout = std::fs::read_to_string(stdout_path).unwrap();
+                    let stderr = std::fs::read_to_string(stderr_path).unwrap();
+                    assert!(stderr.is_empty(), "test {}: {}", t.name, stderr);
+                    if !stdout.contains(&t.want_out) {
+                        assert_eq!(stdout, t.want_out, "test {}: stdout mismatch", t.name);
+                    }
+                }
+                Err(err) => {
+                    let stdout = std::fs::read_to_string(stdout_path).unwrap();
+                    let stderr = std::fs::read_to_string(stderr_path).unwrap();
+                    assert_eq!(stdout, t.want_out, "test {}", t.name);
+                    if !err.to_string().contains(&t.want_err) {
+                        assert_eq!(err.to_string(), t.want_err, "test {}: err mismatch", t.name);
+                    }
+                    assert!(stderr.is_empty(), "test {}: {}", t.name, stderr);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use cli_macro::crud_gen;
+
+/// List, view, and watch sagas.
+///
+/// Sagas are the multi-step operations the control plane runs on your behalf, like
+/// provisioning or starting an instance.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdSaga {
+    #[clap(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[crud_gen {
+    tag = "sagas",
+}]
+#[derive(Parser, Debug, Clone)]
+enum SubCommand {
+    Watch(CmdSagaWatch),
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdSaga {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        match &self.subcmd {
+            SubCommand::List(cmd) => cmd.run(ctx).await,
+            SubCommand::View(cmd) => cmd.run(ctx).await,
+            SubCommand::Watch(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+/// How long to watch a saga for before giving up.
+const SAGA_WATCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// Watch a saga until it finishes.
+///
+/// The state of the saga is polled and shown until it either succeeds or fails.
+/// If the saga fails, the node it failed at and the error are printed and the
+/// command exits with a non-zero status. Watching gives up after 10 minutes.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdSagaWatch {
+    /// The ID of the saga to watch.
+    #[clap(name = "saga", required = true)]
+    pub saga: String,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdSagaWatch {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if uuid::Uuid::parse_str(&self.saga).is_err() {
+            return Err(anyhow!("saga must be an ID, got {}", self.saga));
+        }
+
+        // Start the progress bar.
+        let handle = ctx
+            .io
+            .start_process_indicator_with_label(&format!(" Watching saga {}", self.saga));
+
+        let client = ctx.api_client("")?;
+
+        let started = std::time::Instant::now();
+        let mut last_state = None;
+        loop {
+            let saga = match client.sagas().get(&self.saga).await {
+                Ok(saga) => saga,
+                Err(err) => {
+                    if let Some(handle) = handle {
+                        handle.text(format!("Watching saga {} failed", self.saga));
+                        handle.error();
+                    }
+
+                    return Err(err);
+                }
+            };
+
+            let state = saga_state_name(&saga.state);
+            if last_state != Some(state) {
+                if let Some(handle) = &handle {
+                    handle.text(format!(" Watching saga {} [{}]", self.saga, state));
+                }
+                last_state = Some(state);
+            }
+
+            match saga.state {
+                oxide_api::types::SagaState::Running { .. } => {}
+                oxide_api::types::SagaState::Succeeded { .. } => {
+                    // End the progress bar.
+                    if let Some(handle) = handle {
+                        handle.text(format!("Saga {} succeeded", self.saga));
+                        handle.done();
+                    }
+
+                    return Ok(());
+                }
+                oxide_api::types::SagaState::Failed {
+                    error_info,
+                    error_node_name,
+                } => {
+                    if let Some(handle) = handle {
+                        handle.text(format!("Saga {} failed", self.saga));
+                        handle.error();
+                    }
+
+                    return Err(anyhow!(
+                        "saga {} failed at node {}: {}",
+                        self.saga,
+                        error_node_name,
+                        serde_json::to_string(&error_info)?
+                    ));
+                }
+            }
+
+            if started.elapsed() > SAGA_WATCH_TIMEOUT {
+                if let Some(handle) = handle {
+                    handle.text(format!("Saga {} still {}", self.saga, state));
+                    handle.error();
+                }
+
+                return Err(anyhow!(
+                    "timed out after {}s waiting for saga {} to finish",
+                    SAGA_WATCH_TIMEOUT.as_secs(),
+                    self.saga
+                ));
+            }
+
+            // Back off a bit.
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    }
+}
+
+/// Returns the name of the state, as the API spells it.
+fn saga_state_name(state: &oxide_api::types::SagaState) -> &'static str {
+    match state {
+        oxide_api::types::SagaState::Running { .. } => "running",
+        oxide_api::types::SagaState::Succeeded { .. } => "succeeded",
+        oxide_api::types::SagaState::Failed { .. } => "failed",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::cmd::Command;
+
+    pub struct TestItem {
+        name: String,
+        cmd: crate::cmd_saga::SubCommand,
+        stdin: String,
+        want_out: String,
+        want_err: String,
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_cmd_saga() {
+        let tests: Vec<TestItem> = vec![
+            TestItem {
+                name: "list zero limit".to_string(),
+                cmd: crate::cmd_saga::SubCommand::List(crate::cmd_saga::CmdSagaList {
+                    sort_by: Default::default(),
+                    limit: 0,
+                    paginate: false,
+                    format: None,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--limit must be greater than 0".to_string(),
+            },
+            TestItem {
+                name: "watch invalid id".to_string(),
+                cmd: crate::cmd_saga::SubCommand::Watch(crate::cmd_saga::CmdSagaWatch {
+                    saga: "instance-create".to_string(),
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "saga must be an ID, got instance-create".to_string(),
+            },
+        ];
+
+        let mut config = crate::config::new_blank_config().unwrap();
+        let mut c = crate::config_from_env::EnvConfig::inherit_env(&mut config);
+
+        for t in tests {
+            let (mut io, stdout_path, stderr_path) = crate::iostreams::IoStreams::test();
+            if !t.stdin.is_empty() {
+                io.stdin = Box::new(std::io::Cursor::new(t.stdin));
+            }
+            // We need to also turn off the fancy terminal colors.
+            // This ensures it also works in GitHub actions/any CI.
+            io.set_color_enabled(false);
+            io.set_never_prompt(true);
+            let mut ctx = crate::context::Context {
+                config: &mut c,
+                io,
+                debug: false,
+            };
+
+            let cmd_saga = crate::cmd_saga::CmdSaga { subcmd: t.cmd };
+            match cmd_saga.run(&mut ctx).await {
+                Ok(()) => {
+                    let stdout = std::fs::read_to_string(stdout_path).unwrap();
+                    let stderr = std::fs::read_to_string(stderr_path).unwrap();
+                    assert!(stderr.is_empty(), "test {}: {}", t.name, stderr);
+                    if !stdout.contains(&t.want_out) {
+                        assert_eq!(stdout, t.want_out, "test {}: stdout mismatch", t.name);
+                    }
+                }
+                Err(err) => {
+                    let stdout = std::fs::read_to_string(stdout_path).unwrap();
+                    let stderr = std::fs::read_to_string(stderr_path).unwrap();
+                    assert_eq!(stdout, t.want_out, "test {}", t.name);
+                    if !err.to_string().contains(&t.want_err) {
+                        assert_eq!(err.to_string(), t.want_err, "test {}: err mismatch", t.name);
+                    }
+                    assert!(stderr.is_empty(), "test {}: {}", t.name, stderr);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use std::{io::Write, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+
+/// Generate an OpenSSH config block for the instances of a project.
+///
+/// Each instance with an external IP gets a `Host <organization>.<project>.<instance>`
+/// stanza, so that plain **ssh(1)**, **scp(1)**, and **rsync(1)** can reach it by name.
+/// Instances without an external IP are skipped.
+///
+/// The block is printed by default. With `--write`, it is merged into your ssh config
+/// file instead, between marker comments for the project, so running the command
+/// again replaces the block rather than adding another one.
+///
+///     # print the block for a project
+///     $ oxide ssh-config -o foo -p bar
+///
+///     # merge it into ~/.ssh/config, then ssh into an instance by name
+///     $ oxide ssh-config -o foo -p bar --write
+///     $ ssh foo.bar.my-instance
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdSshConfig {
+    /// The project to generate the config for.
+    #[clap(long, short, required = true)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// The ssh user. This defaults to `$USER` on the host the command is run on.
+    #[clap(long, short, required = true, env = "USER")]
+    pub user: String,
+
+    /// The private key to authenticate with, like one made by `oxide ssh-key generate`.
+    #[clap(long, short, default_value = "~/.ssh/id_ed25519")]
+    pub identity_file: String,
+
+    /// Merge the block into the ssh config file instead of printing it.
+    #[clap(long, short)]
+    pub write: bool,
+
+    /// The ssh config file to merge the block into. This defaults to `~/.ssh/config`.
+    #[clap(long, requires = "write")]
+    pub config_file: Option<PathBuf>,
+}
+
+/// A host to write a stanza for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SshHost {
+    /// The name to reach the host by.
+    name: String,
+    /// The address of the host.
+    ip: String,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdSshConfig {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let client = ctx.api_client("")?;
+
+        let instances = client
+            .instances()
+            .get_all(
+                &self.organization,
+                &self.project,
+                oxide_api::types::NameSortMode::NameAscending,
+            )
+            .await?;
+
+        let cs = ctx.io.color_scheme();
+
+        let mut hosts = Vec::new();
+        for instance in instances {
+            let external_ips = client
+                .instances()
+                .external_ip_list(&instance.name, &self.organization, &self.project)
+                .await?;
+
+            match external_ips.items.first() {
+                Some(ip) => hosts.push(SshHost {
+                    name: format!("{}.{}.{}", self.organization, self.project, instance.name),
+                    ip: ip.ip.to_string(),
+                }),
+                None => {
+                    writeln!(
+                        ctx.io.err_out,
+                        "{} Skipping instance {}, it has no external IP",
+                        cs.warning_icon(),
+                        instance.name
+                    )?;
+                }
+            }
+        }
+
+        let block = render_block(
+            &self.organization,
+            &self.project,
+            &self.user,
+            &self.identity_file,
+            &hosts,
+        );
+
+        if !self.write {
+            write!(ctx.io.out, "{}", block)?;
+            return Ok(());
+        }
+
+        let config_file = match &self.config_file {
+            Some(config_file) => config_file.clone(),
+            None => match dirs::home_dir() {
+                Some(home) => home.join(".ssh").join("config"),
+                None => return Err(anyhow!("could not find your home directory, use --config-file")),
+            },
+        };
+
+        let existing = match std::fs::read_to_string(&config_file) {
+            Ok(existing) => existing,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(anyhow!("reading {} failed: {}", config_file.display(), err)),
+        };
+
+        let merged = merge_block(&existing, &self.organization, &self.project, &block)
+            .map_err(|err| anyhow!("{}: {}", config_file.display(), err))?;
+
+        if let Some(parent) = config_file.parent() {
+            create_private_dir(parent)?;
+        }
+        write_atomic(&config_file, &merged)?;
+
+        writeln!(
+            ctx.io.out,
+            "{} Wrote {} hosts for {}/{} to {}",
+            cs.success_icon(),
+            hosts.len(),
+            self.organization,
+            self.project,
+            config_file.display()
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Returns the marker comments the block for the project is wrapped in.
+fn markers(organization: &str, project: &str) -> (String, String) {
+    (
+        format!("# BEGIN oxide ssh-config {}/{}", organization, project),
+        format!("# END oxide ssh-config {}/{}", organization, project),
+    )
+}
+
+/// Render the block of stanzas for the hosts, wrapped in the marker comments for the
+/// project.
+fn render_block(organization: &str, project: &str, user: &str, identity_file: &str, hosts: &[SshHost]) -> String {
+    let (begin, end) = markers(organization, project);
+
+    let mut block = format!(
+        "{}\n# This block is managed by `oxide ssh-config`, edits will be lost.\n",
+        begin
+    );
+    for host in hosts {
+        block.push_str(&format!(
+            "Host {}\n    HostName {}\n    User {}\n    IdentityFile {}\n",
+            host.name, host.ip, user, identity_file
+        ));
+    }
+    block.push_str(&end);
+    block.push('\n');
+
+    block
+}
+
+/// Merge the block for the project into the existing config, replacing the block
+/// between the project's markers if there is one, and appending it otherwise.
+///
+/// A begin marker without its end marker, say after a hand edit, is an error rather
+/// than a reason to append a second block.
+fn merge_block(existing: &str, organization: &str, project: &str, block: &str) -> Result<String> {
+    let (begin, end) = markers(organization, project);
+
+    let lines = existing.lines().collect::<Vec<_>>();
+    let start = lines.iter().position(|line| line.trim() == begin);
+    let stop = start.and_then(|start| {
+        lines[start..]
+            .iter()
+            .position(|line| line.trim() == end)
+            .map(|i| start + i)
+    });
+
+    let mut merged = String::new();
+    match (start, stop) {
+        (Some(start), Some(stop)) => {
+            for line in &lines[..start] {
+                merged.push_str(line);
+                merged.push('\n');
+            }
+            merged.push_str(block);
+            for line in &lines[stop + 1..] {
+                merged.push_str(line);
+                merged.push('\n');
+            }
+        }
+        (Some(_), None) => {
+            return Err(anyhow!(
+                "found `{}` without a matching `{}`, fix or remove the block by hand",
+                begin,
+                end
+            ));
+        }
+        (None, _) => {
+            merged.push_str(existing);
+            if !merged.is_empty() && !merged.ends_with('\n') {
+                merged.push('\n');
+            }
+            if !merged.is_empty() {
+                merged.push('\n');
+            }
+            merged.push_str(block);
+        }
+    }
+
+    Ok(merged)
+}
+
+/// Create the directory and any missing parents, only readable by the user, as ssh
+/// expects of `~/.ssh`. Directories that already exist are left alone.
+fn create_private_dir(dir: &std::path::Path) -> Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+    builder
+        .create(dir)
+        .map_err(|err| anyhow!("creating {} failed: {}", dir.display(), err))
+}
+
+/// Replace the contents of the file by writing them to a temporary file next to it
+/// and renaming that over it, so the file is never left half written. If the file is
+/// a symlink, the file it points to is replaced, and its permissions are kept.
+fn write_atomic(path: &std::path::Path, contents: &str) -> Result<()> {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| anyhow!("{} is not a file", path.display()))?;
+    let temp = path.with_file_name(format!(".{}.oxide-tmp", file_name.to_string_lossy()));
+
+    let result = (|| -> std::io::Result<()> {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(&temp)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+
+        if let Ok(metadata) = std::fs::metadata(&path) {
+            std::fs::set_permissions(&temp, metadata.permissions())?;
+        }
+
+        std::fs::rename(&temp, &path)
+    })();
+
+    if let Err(err) = result {
+        let _ = std::fs::remove_file(&temp);
+        return Err(anyhow!("writing {} failed: {}", path.display(), err));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    fn hosts() -> Vec<super::SshHost> {
+        vec![
+            super::SshHost {
+                name: "foo.bar.db".to_string(),
+                ip: "172.20.15.227".to_string(),
+            },
+            super::SshHost {
+                name: "foo.bar.web".to_string(),
+                ip: "172.20.15.228".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_block() {
+        let block = super::render_block("foo", "bar", "alice", "~/.ssh/id_ed25519", &hosts());
+        assert_eq!(
+            block,
+            r#"# BEGIN oxide ssh-config foo/bar
+# This block is managed by `oxide ssh-config`, edits will be lost.
+Host foo.bar.db
+    HostName 172.20.15.227
+    User alice
+    IdentityFile ~/.ssh/id_ed25519
+Host foo.bar.web
+    HostName 172.20.15.228
+    User alice
+    IdentityFile ~/.ssh/id_ed25519
+# END oxide ssh-config foo/bar
+"#
+        );
+    }
+
+    #[test]
+    fn test_merge_block() {
+        let block = super::render_block("foo", "bar", "alice", "~/.ssh/id_ed25519", &hosts());
+
+        // Into an empty config.
+        assert_eq!(super::merge_block("", "foo", "bar", &block).unwrap(), block);
+
+        // Appended to an existing config.
+        let existing = "Host github.com\n    User git";
+        let merged = super::merge_block(existing, "foo", "bar", &block).unwrap();
+        assert_eq!(merged, format!("{}\n\n{}", existing, block));
+
+        // Merging again replaces the block rather than adding another one.
+        let other = super::render_block("foo", "bar", "alice", "~/.ssh/id_ed25519", &hosts()[..1]);
+        let remerged = super::merge_block(&merged, "foo", "bar", &other).unwrap();
+        assert_eq!(remerged, format!("{}\n\n{}", existing, other));
+        assert_eq!(super::merge_block(&remerged, "foo", "bar", &other).unwrap(), remerged);
+
+        // Blocks for other projects are left alone.
+        let baz = super::render_block("foo", "baz", "alice", "~/.ssh/id_ed25519", &hosts());
+        let both = super::merge_block(&merged, "foo", "baz", &baz).unwrap();
+        assert_eq!(both, format!("{}\n\n{}\n{}", existing, block, baz));
+        assert_eq!(
+            super::merge_block(&both, "foo", "bar", &other).unwrap(),
+            format!("{}\n\n{}\n{}", existing, other, baz)
+        );
+
+        // A block whose end marker was removed is not merged into.
+        let unterminated = format!("{}\n\n# BEGIN oxide ssh-config foo/bar\nHost foo.bar.db\n", existing);
+        assert_eq!(
+            super::merge_block(&unterminated, "foo", "bar", &block)
+                .unwrap_err()
+                .to_string(),
+            "found `# BEGIN oxide ssh-config foo/bar` without a matching `# END oxide ssh-config foo/bar`, fix or \
+             remove the block by hand"
+        );
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let dir = tempfile::tempdir().unwrap();
+        let ssh_dir = dir.path().join(".ssh");
+        let config = ssh_dir.join("config");
+
+        super::create_private_dir(&ssh_dir).unwrap();
+        super::write_atomic(&config, "Host a\n").unwrap();
+        super::write_atomic(&config, "Host b\n").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&config).unwrap(), "Host b\n");
+        assert_eq!(std::fs::read_dir(&ssh_dir).unwrap().count(), 1);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = |path: &std::path::Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&ssh_dir), 0o700);
+            assert_eq!(mode(&config), 0o600);
+        }
+    }
+}
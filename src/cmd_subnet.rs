@@ -1,10 +1,12 @@
-use std::io::Write;
+use std::{collections::HashMap, io::Write, net::IpAddr};
 
 use anyhow::Result;
 use clap::Parser;
 use cli_macro::crud_gen;
 
 /// Create, list, edit, view, and delete subnets.
+///
+/// Additionally, list the network interfaces in a subnet.
 #[derive(Parser, Debug, Clone)]
 #[clap(verbatim_doc_comment)]
 pub struct CmdSubnet {
@@ -16,7 +18,9 @@ pub struct CmdSubnet {
     tag = "subnets",
 }]
 #[derive(Parser, Debug, Clone)]
-enum SubCommand {}
+enum SubCommand {
+    Interfaces(CmdSubnetInterfaces),
+}
 
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdSubnet {
@@ -25,14 +29,134 @@ impl crate::cmd::Command for CmdSubnet {
             SubCommand::Create(cmd) => cmd.run(ctx).await,
             SubCommand::Delete(cmd) => cmd.run(ctx).await,
             SubCommand::Edit(cmd) => cmd.run(ctx).await,
+            SubCommand::Interfaces(cmd) => cmd.run(ctx).await,
             SubCommand::List(cmd) => cmd.run(ctx).await,
             SubCommand::View(cmd) => cmd.run(ctx).await,
         }
     }
 }
 
+/// List the network interfaces in a subnet.
+///
+/// Each interface is shown with its IP and MAC address and the instance it belongs
+/// to. For table output, a summary of how much of the IPv4 block of the subnet is in
+/// use follows.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdSubnetInterfaces {
+    /// The subnet to list the network interfaces of.
+    #[clap(name = "subnet", required = true)]
+    pub subnet: String,
+
+    /// The VPC that holds the subnet.
+    #[clap(long, short, required = true)]
+    pub vpc: String,
+
+    /// The project that holds the VPC.
+    #[clap(long, short, required = true)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+/// A network interface in a subnet.
+#[derive(Debug, Clone, serde::Serialize, tabled::Tabled)]
+struct SubnetInterface {
+    name: String,
+    instance: String,
+    ip: String,
+    mac: String,
+    primary: bool,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdSubnetInterfaces {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let client = ctx.api_client("")?;
+
+        let subnet = client
+            .subnets()
+            .get(&self.organization, &self.project, &self.subnet, &self.vpc)
+            .await?;
+
+        let nics = client
+            .subnets()
+            .network_interfaces_get_all(
+                &self.organization,
+                &self.project,
+                oxide_api::types::NameSortMode::NameAscending,
+                &self.subnet,
+                &self.vpc,
+            )
+            .await?;
+
+        // Look up the names of the instances the interfaces belong to, most instances
+        // only have one interface in a subnet but we only need to ask once.
+        let mut instance_names: HashMap<String, String> = HashMap::new();
+        let mut interfaces = Vec::new();
+        for nic in &nics {
+            let instance_id = nic.instance_id.to_string();
+            if !instance_names.contains_key(&instance_id) {
+                let instance = client.instances().view_by_id(&instance_id).await?;
+                instance_names.insert(instance_id.to_string(), instance.name.to_string());
+            }
+
+            interfaces.push(SubnetInterface {
+                name: nic.name.to_string(),
+                instance: instance_names[&instance_id].to_string(),
+                ip: nic.ip.to_string(),
+                mac: nic.mac.to_string(),
+                primary: nic.primary,
+            });
+        }
+
+        let format = ctx.format(&self.format)?;
+        ctx.io.write_output_for_vec(&format, &interfaces)?;
+
+        if format == crate::types::FormatOutput::Table {
+            let ips: Vec<IpAddr> = nics.iter().map(|nic| nic.ip).collect();
+            let (used, usable) = ipv4_usage(&subnet.ipv4_block.0, &ips);
+            let percent = if usable == 0 {
+                0.0
+            } else {
+                used as f64 * 100.0 / usable as f64
+            };
+            writeln!(
+                ctx.io.out,
+                "{} of {} usable addresses in {} in use ({:.1}%)",
+                used, usable, subnet.ipv4_block, percent
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns how many of the addresses are assignable addresses of the IPv4 block, and
+/// how many addresses of the block can be assigned to interfaces.
+fn ipv4_usage(block: &ipnetwork::Ipv4Network, ips: &[IpAddr]) -> (u64, u64) {
+    let used = ips
+        .iter()
+        .filter(|ip| match ip {
+            IpAddr::V4(ip) => block.contains(*ip) && !crate::cmd_instance_nic::is_reserved_ipv4(block, *ip),
+            IpAddr::V6(_) => false,
+        })
+        .count() as u64;
+    let usable = (block.size() as u64).saturating_sub(crate::cmd_instance_nic::RESERVED_SUBNET_ADDRESSES + 1);
+
+    (used, usable)
+}
+
 #[cfg(test)]
 mod test {
+    use std::str::FromStr;
+
     use pretty_assertions::assert_eq;
 
     use crate::cmd::Command;
@@ -203,4 +327,19 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_ipv4_usage() {
+        let block = ipnetwork::Ipv4Network::from_str("172.30.0.0/28").unwrap();
+        let ips: Vec<std::net::IpAddr> = ["172.30.0.5", "172.30.0.6", "172.30.1.5", "fd00::5"]
+            .iter()
+            .map(|ip| std::net::IpAddr::from_str(ip).unwrap())
+            .collect();
+
+        assert_eq!(crate::cmd_subnet::ipv4_usage(&block, &ips), (2, 10));
+        assert_eq!(crate::cmd_subnet::ipv4_usage(&block, &[]), (0, 10));
+
+        let tiny = ipnetwork::Ipv4Network::from_str("172.30.0.0/30").unwrap();
+        assert_eq!(crate::cmd_subnet::ipv4_usage(&tiny, &[]), (0, 0));
+    }
 }
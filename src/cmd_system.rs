@@ -0,0 +1,28 @@
+use anyhow::Result;
+use clap::Parser;
+
+/// Manage the system as a whole.
+///
+/// These commands act on the rack rather than on the CLI itself. To update the
+/// CLI, use `oxide update` instead.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdSystem {
+    #[clap(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser, Debug, Clone)]
+enum SubCommand {
+    #[clap(alias = "updates")]
+    Update(crate::cmd_system_update::CmdSystemUpdate),
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdSystem {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        match &self.subcmd {
+            SubCommand::Update(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::io::Write;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+
+/// Manage updates to the system software.
+///
+/// These update the software running on the rack, not the CLI. To update the CLI,
+/// use `oxide update` instead.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdSystemUpdate {
+    #[clap(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser, Debug, Clone)]
+enum SubCommand {
+    Refresh(CmdSystemUpdateRefresh),
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdSystemUpdate {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        match &self.subcmd {
+            SubCommand::Refresh(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+/// How long to keep looking for new sagas after the refresh was requested, since the
+/// system may take a moment to start them.
+const REFRESH_SAGA_WINDOW: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Refresh the data about available system updates.
+///
+/// This asks the system to look for new updates, then watches the sagas that
+/// appear shortly after the refresh was requested until they finish.
+///
+/// Sagas don't say what started them, so they can't be narrowed down to the
+/// refresh, and some of those watched may belong to other work, like creating an
+/// instance. If any of the watched sagas fail, the command exits with a non-zero
+/// status, even though the failure may be unrelated to the refresh.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdSystemUpdateRefresh {
+    /// Return once the refresh is requested, without watching its sagas.
+    #[clap(long)]
+    pub no_watch: bool,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdSystemUpdateRefresh {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let client = ctx.api_client("")?;
+
+        let mut seen = if self.no_watch {
+            Vec::new()
+        } else {
+            saga_ids(&client).await?
+        };
+
+        client.updates().refresh().await?;
+        let deadline = std::time::Instant::now() + REFRESH_SAGA_WINDOW;
+
+        let cs = ctx.io.color_scheme();
+        writeln!(ctx.io.out, "{} Requested a refresh of update data", cs.success_icon())?;
+
+        if self.no_watch {
+            return Ok(());
+        }
+
+        // The sagas of the refresh may start a little after it was requested, so keep
+        // looking for new ones for a while, watching each as it shows up.
+        let mut watched = 0;
+        let mut failed = 0;
+        loop {
+            let sagas = new_sagas(&seen, &saga_ids(&client).await?);
+            for saga in sagas {
+                seen.push(saga.clone());
+                watched += 1;
+
+                let cmd = crate::cmd_saga::CmdSagaWatch { saga };
+                if let Err(err) = crate::cmd::Command::run(&cmd, ctx).await {
+                    writeln!(ctx.io.err_out, "{} {}", cs.failure_icon(), err)?;
+                    failed += 1;
+                }
+            }
+
+            if std::time::Instant::now() >= deadline {
+                break;
+            }
+
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+
+        if watched == 0 {
+            writeln!(ctx.io.out, "No new sagas were started")?;
+        }
+
+        if failed > 0 {
+            return Err(anyhow!(
+                "{} of the {} sagas watched failed, they may be unrelated to the refresh",
+                failed,
+                watched
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the IDs of all the sagas the system knows about.
+async fn saga_ids(client: &oxide_api::Client) -> Result<Vec<String>> {
+    let sagas = client
+        .sagas()
+        .get_all(oxide_api::types::IdSortMode::IdAscending)
+        .await?;
+
+    Ok(sagas.into_iter().map(|s| s.id).collect())
+}
+
+/// Returns the IDs in `after` that are not in `before`.
+fn new_sagas(before: &[String], after: &[String]) -> Vec<String> {
+    after.iter().filter(|id| !before.contains(id)).cloned().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_new_sagas() {
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            super::new_sagas(&ids(&["a", "b"]), &ids(&["a", "c", "b", "d"])),
+            ids(&["c", "d"])
+        );
+        assert!(super::new_sagas(&ids(&["a", "b"]), &ids(&["a", "b"])).is_empty());
+        assert_eq!(super::new_sagas(&[], &ids(&["a"])), ids(&["a"]));
+    }
+}
@@ -5,6 +5,9 @@ use clap::Parser;
 ///
 /// This function will return an error if the current binary is under Homebrew or if
 /// the running version is already the latest version.
+///
+/// This only updates the CLI. To update the software running on the rack, use
+/// `oxide system update` instead.
 #[derive(Parser, Debug, Clone)]
 #[clap(verbatim_doc_comment)]
 pub struct CmdUpdate {}
@@ -0,0 +1,244 @@
+use anyhow::{anyhow, Result};
+use clap::Parser;
+
+/// List and view users.
+///
+/// By default these are the users of the silo you are logged in to. With `--system`,
+/// they are the built-in users of the system instead.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdUser {
+    #[clap(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser, Debug, Clone)]
+enum SubCommand {
+    List(CmdUserList),
+    #[clap(alias = "get")]
+    View(CmdUserView),
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdUser {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        match &self.subcmd {
+            SubCommand::List(cmd) => cmd.run(ctx).await,
+            SubCommand::View(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+/// List users.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdUserList {
+    /// List the built-in users of the system instead.
+    #[clap(long)]
+    pub system: bool,
+
+    /// Maximum number of items to list.
+    #[clap(long, short, default_value = "30")]
+    pub limit: u32,
+
+    /// Make additional HTTP requests to fetch all pages.
+    #[clap(long)]
+    pub paginate: bool,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdUserList {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if self.limit < 1 {
+            return Err(anyhow!("--limit must be greater than 0"));
+        }
+
+        let client = ctx.api_client("")?;
+        let format = ctx.format(&self.format)?;
+
+        if self.system {
+            let results = if self.paginate {
+                client
+                    .system()
+                    .user_list_all(oxide_api::types::NameSortMode::NameAscending)
+                    .await?
+            } else {
+                client
+                    .system()
+                    .user_list(self.limit, "", oxide_api::types::NameSortMode::NameAscending)
+                    .await?
+            };
+
+            ctx.io.write_output_for_vec(&format, &results)?;
+            return Ok(());
+        }
+
+        let results = if self.paginate {
+            client
+                .silos()
+                .users_get_all(oxide_api::types::IdSortMode::IdAscending)
+                .await?
+        } else {
+            client
+                .silos()
+                .users_get(self.limit, "", oxide_api::types::IdSortMode::IdAscending)
+                .await?
+        };
+
+        ctx.io.write_output_for_vec(&format, &results)?;
+        Ok(())
+    }
+}
+
+/// View a user.
+///
+/// Users of the silo are found by their ID or display name. Built-in users of the
+/// system are found by their name.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdUserView {
+    /// The user to view.
+    #[clap(name = "user", required = true)]
+    pub user: String,
+
+    /// View a built-in user of the system instead.
+    #[clap(long)]
+    pub system: bool,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdUserView {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let client = ctx.api_client("")?;
+        let format = ctx.format(&self.format)?;
+
+        if self.system {
+            let result = client.system().user_view(&self.user).await?;
+            ctx.io.write_output(&format, &result)?;
+            return Ok(());
+        }
+
+        let result = find_silo_user(&client, &self.user).await?;
+        ctx.io.write_output(&format, &result)?;
+        Ok(())
+    }
+}
+
+/// Find a user of the current silo by their ID or display name.
+///
+/// The API can only list the users of a silo, so we look through all of them.
+pub async fn find_silo_user(client: &oxide_api::Client, user: &str) -> Result<oxide_api::types::User> {
+    let users = client
+        .silos()
+        .users_get_all(oxide_api::types::IdSortMode::IdAscending)
+        .await?;
+
+    match_user(users, user)
+}
+
+/// Pick the user with the ID or display name out of the users. IDs win over display
+/// names, which are not necessarily unique.
+fn match_user(users: Vec<oxide_api::types::User>, user: &str) -> Result<oxide_api::types::User> {
+    if let Some(found) = users.iter().find(|u| u.id == user) {
+        return Ok(found.clone());
+    }
+
+    let mut matches = users.into_iter().filter(|u| u.display_name == user).collect::<Vec<_>>();
+    match matches.len() {
+        0 => Err(anyhow!("no user found with ID or display name {}", user)),
+        1 => Ok(matches.remove(0)),
+        n => Err(anyhow!(
+            "{} users have the display name {}, use their ID instead",
+            n,
+            user
+        )),
+    }
+}
+
+/// Returns the name to show for a user, their display name if they have one and
+/// their ID otherwise.
+pub fn display_name(user: &oxide_api::types::User) -> String {
+    if user.display_name.is_empty() {
+        user.id.to_string()
+    } else {
+        user.display_name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::cmd::Command;
+
+    fn user(id: &str, display_name: &str) -> oxide_api::types::User {
+        oxide_api::types::User {
+            id: id.to_string(),
+            display_name: display_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_match_user() {
+        let users = vec![
+            user("4b0a4a5e-7d8c-4a3c-9a6f-2f1d6c1e0a01", "alice"),
+            user("9d2e7f3a-1b5c-4e8d-8f0a-6c3b2a1d4e02", "bob"),
+            user("0c6f1e2d-3a4b-4c5d-9e6f-7a8b9c0d1e03", "bob"),
+        ];
+
+        let found = super::match_user(users.clone(), "alice").unwrap();
+        assert_eq!(found.id, "4b0a4a5e-7d8c-4a3c-9a6f-2f1d6c1e0a01");
+
+        let found = super::match_user(users.clone(), "0c6f1e2d-3a4b-4c5d-9e6f-7a8b9c0d1e03").unwrap();
+        assert_eq!(found.display_name, "bob");
+
+        let err = super::match_user(users.clone(), "bob").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2 users have the display name bob, use their ID instead"
+        );
+
+        let err = super::match_user(users, "carol").unwrap_err();
+        assert_eq!(err.to_string(), "no user found with ID or display name carol");
+    }
+
+    #[test]
+    fn test_display_name() {
+        assert_eq!(super::display_name(&user("some-id", "alice")), "alice");
+        assert_eq!(super::display_name(&user("some-id", "")), "some-id");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_cmd_user_list_zero_limit() {
+        let mut config = crate::config::new_blank_config().unwrap();
+        let mut c = crate::config_from_env::EnvConfig::inherit_env(&mut config);
+
+        let (mut io, stdout_path, _stderr_path) = crate::iostreams::IoStreams::test();
+        io.set_color_enabled(false);
+        io.set_never_prompt(true);
+        let mut ctx = crate::context::Context {
+            config: &mut c,
+            io,
+            debug: false,
+        };
+
+        let cmd = crate::cmd_user::CmdUserList {
+            system: false,
+            limit: 0,
+            paginate: false,
+            format: None,
+        };
+
+        let err = cmd.run(&mut ctx).await.unwrap_err();
+        assert_eq!(err.to_string(), "--limit must be greater than 0");
+        assert!(std::fs::read_to_string(stdout_path).unwrap().is_empty());
+    }
+}
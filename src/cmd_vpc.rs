@@ -5,6 +5,8 @@ use clap::Parser;
 use cli_macro::crud_gen;
 
 /// Create, list, edit, view, and delete VPCs.
+///
+/// Additionally, export and apply the firewall rules of VPCs.
 #[derive(Parser, Debug, Clone)]
 #[clap(verbatim_doc_comment)]
 pub struct CmdVpc {
@@ -16,7 +18,9 @@ pub struct CmdVpc {
     tag = "vpcs",
 }]
 #[derive(Parser, Debug, Clone)]
-enum SubCommand {}
+enum SubCommand {
+    Firewall(crate::cmd_vpc_firewall::CmdVpcFirewall),
+}
 
 #[async_trait::async_trait]
 impl crate::cmd::Command for CmdVpc {
@@ -25,6 +29,7 @@ impl crate::cmd::Command for CmdVpc {
             SubCommand::Create(cmd) => cmd.run(ctx).await,
             SubCommand::Delete(cmd) => cmd.run(ctx).await,
             SubCommand::Edit(cmd) => cmd.run(ctx).await,
+            SubCommand::Firewall(cmd) => cmd.run(ctx).await,
             SubCommand::List(cmd) => cmd.run(ctx).await,
             SubCommand::View(cmd) => cmd.run(ctx).await,
         }
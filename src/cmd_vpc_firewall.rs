@@ -0,0 +1,953 @@
+use std::io::{Read, Write};
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use parse_display::{Display, FromStr};
+use serde::{Deserialize, Serialize};
+
+/// Export and apply the firewall rules of a VPC.
+///
+/// The rules of a VPC are replaced as a whole, so they are best kept in a file that
+/// is reviewed in version control. Use `export` to write the current rules to a
+/// file, `lint` to check them for mistakes, and `apply` to make the VPC match the
+/// file.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdVpcFirewall {
+    #[clap(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Parser, Debug, Clone)]
+enum SubCommand {
+    Apply(CmdVpcFirewallApply),
+    Export(CmdVpcFirewallExport),
+    Lint(CmdVpcFirewallLint),
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdVpcFirewall {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        match &self.subcmd {
+            SubCommand::Apply(cmd) => cmd.run(ctx).await,
+            SubCommand::Export(cmd) => cmd.run(ctx).await,
+            SubCommand::Lint(cmd) => cmd.run(ctx).await,
+        }
+    }
+}
+
+/// Export the firewall rules of a VPC.
+///
+/// The rules are written to stdout in a form that can be passed to
+/// `oxide vpc firewall apply`.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdVpcFirewallExport {
+    /// The VPC to export the firewall rules of.
+    #[clap(name = "vpc", required = true)]
+    pub vpc: String,
+
+    /// The project that holds the VPC.
+    #[clap(long, short, required = true)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// Write the rules in yaml or json format.
+    #[clap(long, short, default_value = "yaml")]
+    pub format: RulesFormat,
+}
+
+/// The formats firewall rules can be written in.
+#[derive(PartialEq, Eq, Debug, Clone, FromStr, Display)]
+#[display(style = "kebab-case")]
+pub enum RulesFormat {
+    Yaml,
+    Json,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdVpcFirewallExport {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let client = ctx.api_client("")?;
+
+        let rules = get_rules(&client, &self.organization, &self.project, &self.vpc).await?;
+
+        match self.format {
+            RulesFormat::Yaml => write!(ctx.io.out, "{}", serde_yaml::to_string(&rules)?)?,
+            RulesFormat::Json => writeln!(ctx.io.out, "{}", serde_json::to_string_pretty(&rules)?)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Apply firewall rules from a file to a VPC.
+///
+/// The rules in the file replace all the rules of the VPC. The changes to the
+/// current rules are shown rule by rule before they are applied. With `--dry-run`,
+/// only the changes are shown.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdVpcFirewallApply {
+    /// The VPC to apply the firewall rules to.
+    #[clap(name = "vpc", required = true)]
+    pub vpc: String,
+
+    /// The project that holds the VPC.
+    #[clap(long, short, required = true)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// The file to read the rules from, in yaml or json format. Pass "-" to read from stdin.
+    #[clap(long, short, required = true)]
+    pub file: String,
+
+    /// Show the changes without applying them.
+    #[clap(long)]
+    pub dry_run: bool,
+
+    /// Apply the rules without prompting for confirmation.
+    #[clap(long)]
+    pub confirm: bool,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdVpcFirewallApply {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        if !self.dry_run && !ctx.io.can_prompt() && !self.confirm {
+            return Err(anyhow!("--confirm required when not running interactively"));
+        }
+
+        let desired = read_rules_file(ctx, &self.file)?;
+
+        let client = ctx.api_client("")?;
+
+        let current = get_rules(&client, &self.organization, &self.project, &self.vpc).await?;
+
+        let diff = diff_rules(&current, &desired);
+        write_rules_diff(ctx, &diff)?;
+
+        let changes = diff.iter().filter(|d| !matches!(d, RuleDiff::Unchanged(_))).count();
+        if changes == 0 {
+            writeln!(
+                ctx.io.out,
+                "Firewall rules of VPC {} are up to date, nothing to change",
+                self.vpc
+            )?;
+            return Ok(());
+        }
+
+        if self.dry_run {
+            return Ok(());
+        }
+
+        if !self.confirm {
+            match dialoguer::Confirm::new()
+                .with_prompt(format!(
+                    "Apply {} change(s) to the firewall rules of VPC {}?",
+                    changes, self.vpc
+                ))
+                .interact()
+            {
+                Ok(true) => {}
+                Ok(false) => {
+                    return Ok(());
+                }
+                Err(err) => {
+                    return Err(anyhow!("prompt failed: {}", err));
+                }
+            }
+        }
+
+        client
+            .vpcs()
+            .firewall_rules_put(
+                &self.organization,
+                &self.project,
+                &self.vpc,
+                &serde_json::from_value(serde_json::to_value(&desired)?)?,
+            )
+            .await?;
+
+        let cs = ctx.io.color_scheme();
+        let full_name = format!("{}/{}", self.organization, self.project);
+        writeln!(
+            ctx.io.out,
+            "{} Applied {} change(s) to the firewall rules of VPC {} in {}",
+            cs.success_icon(),
+            changes,
+            self.vpc,
+            full_name
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Check the firewall rules of a VPC for mistakes.
+///
+/// The rules are read from `--file`, or the current rules of the VPC are used. The
+/// following problems are reported:
+///
+///   - rules that never match because a higher-priority rule matches all of their
+///     traffic first
+///   - allow and deny rules that match some of the same traffic
+///   - rules that refer to subnets or instances that do not exist
+///
+/// The command exits with a non-zero status if any problems are found.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdVpcFirewallLint {
+    /// The VPC the firewall rules are for.
+    #[clap(name = "vpc", required = true)]
+    pub vpc: String,
+
+    /// The project that holds the VPC.
+    #[clap(long, short, required = true)]
+    pub project: String,
+
+    /// The organization that holds the project.
+    #[clap(long, short, required = true, env = "OXIDE_ORG")]
+    pub organization: String,
+
+    /// The file to read the rules from, in yaml or json format. Pass "-" to read from stdin.
+    /// If not specified, the current rules of the VPC are checked.
+    #[clap(long, short)]
+    pub file: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdVpcFirewallLint {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let rules = match &self.file {
+            Some(file) => Some(read_rules_file(ctx, file)?),
+            None => None,
+        };
+
+        let client = ctx.api_client("")?;
+
+        let rules = match rules {
+            Some(rules) => rules,
+            None => get_rules(&client, &self.organization, &self.project, &self.vpc).await?,
+        };
+
+        let subnets: Vec<String> = client
+            .subnets()
+            .get_all(
+                &self.organization,
+                &self.project,
+                oxide_api::types::NameSortMode::NameAscending,
+                &self.vpc,
+            )
+            .await?
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+
+        let instances: Vec<String> = client
+            .instances()
+            .get_all(
+                &self.organization,
+                &self.project,
+                oxide_api::types::NameSortMode::NameAscending,
+            )
+            .await?
+            .into_iter()
+            .map(|i| i.name)
+            .collect();
+
+        let findings = lint_rules(&rules, &self.vpc, &subnets, &instances);
+
+        let cs = ctx.io.color_scheme();
+        if findings.is_empty() {
+            writeln!(
+                ctx.io.out,
+                "{} No problems found in {} firewall rule(s) of VPC {}",
+                cs.success_icon(),
+                rules.rules.len(),
+                self.vpc
+            )?;
+            return Ok(());
+        }
+
+        for finding in &findings {
+            writeln!(
+                ctx.io.out,
+                "{} {}: {}",
+                cs.failure_icon(),
+                cs.bold(&finding.rule),
+                finding.message
+            )?;
+        }
+
+        Err(anyhow!(
+            "found {} problem(s) in the firewall rules of VPC {}",
+            findings.len(),
+            self.vpc
+        ))
+    }
+}
+
+/// A problem with a firewall rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    pub rule: String,
+    pub message: String,
+}
+
+/// Check the rules for the problems described in `CmdVpcFirewallLint`. `subnets` are
+/// the subnets of the VPC and `instances` the instances of its project.
+pub fn lint_rules(rules: &FirewallRules, vpc: &str, subnets: &[String], instances: &[String]) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+
+    let enabled: Vec<&FirewallRule> = rules.rules.iter().filter(|r| r.status == RuleStatus::Enabled).collect();
+
+    // Rules that never match.
+    let mut shadowed = Vec::new();
+    for rule in &enabled {
+        if let Some(by) = enabled
+            .iter()
+            .find(|other| other.priority < rule.priority && other.shadows(rule, vpc))
+        {
+            findings.push(LintFinding {
+                rule: rule.name.to_string(),
+                message: format!(
+                    "never matches, all of its traffic is matched first by {} rule {} (priority {})",
+                    by.action, by.name, by.priority
+                ),
+            });
+            shadowed.push(rule.name.as_str());
+        }
+    }
+
+    // Allow and deny rules for the same traffic. We don't repeat this for rules we
+    // already reported as never matching.
+    for (i, a) in enabled.iter().enumerate() {
+        for b in &enabled[i + 1..] {
+            if a.action == b.action
+                || shadowed.contains(&a.name.as_str())
+                || shadowed.contains(&b.name.as_str())
+                || !a.overlaps(b, vpc)
+            {
+                continue;
+            }
+
+            let (first, second) = if a.priority <= b.priority { (a, b) } else { (b, a) };
+            let message = if first.priority == second.priority {
+                format!(
+                    "matches some of the same traffic as {} rule {} with the same priority {}, which one applies is undefined",
+                    first.action, first.name, first.priority
+                )
+            } else {
+                format!(
+                    "matches some of the same traffic as {} rule {} (priority {}), which takes precedence",
+                    first.action, first.name, first.priority
+                )
+            };
+
+            findings.push(LintFinding {
+                rule: second.name.to_string(),
+                message,
+            });
+        }
+    }
+
+    // References to things that are gone.
+    for rule in &rules.rules {
+        let hosts = rule
+            .targets
+            .iter()
+            .map(|h| ("target", h))
+            .chain(rule.filters.hosts.iter().flatten().map(|h| ("host", h)));
+
+        for (kind, host) in hosts {
+            match host {
+                RuleHost::Subnet(subnet) if !subnets.contains(subnet) => findings.push(LintFinding {
+                    rule: rule.name.to_string(),
+                    message: format!("{} subnet {} does not exist in VPC {}", kind, subnet, vpc),
+                }),
+                RuleHost::Instance(instance) if !instances.contains(instance) => findings.push(LintFinding {
+                    rule: rule.name.to_string(),
+                    message: format!("{} instance {} does not exist", kind, instance),
+                }),
+                _ => {}
+            }
+        }
+    }
+
+    findings
+}
+
+impl FirewallRule {
+    /// Returns if this rule matches all the traffic `other` is for.
+    fn shadows(&self, other: &FirewallRule, vpc: &str) -> bool {
+        self.direction == other.direction
+            && covers_all(Some(&self.targets), Some(&other.targets), |a, b| a.covers(b, vpc))
+            && covers_all(self.filters.hosts.as_deref(), other.filters.hosts.as_deref(), |a, b| {
+                a.covers(b, vpc)
+            })
+            && covers_all(
+                self.filters.ports.as_deref(),
+                other.filters.ports.as_deref(),
+                |a, b| match (parse_ports(a), parse_ports(b)) {
+                    (Some(a), Some(b)) => a.0 <= b.0 && b.1 <= a.1,
+                    _ => a == b,
+                },
+            )
+            && covers_all(
+                self.filters.protocols.as_deref(),
+                other.filters.protocols.as_deref(),
+                |a, b| a.eq_ignore_ascii_case(b),
+            )
+    }
+
+    /// Returns if some traffic is matched by both this rule and `other`.
+    fn overlaps(&self, other: &FirewallRule, vpc: &str) -> bool {
+        self.direction == other.direction
+            && overlaps_any(Some(&self.targets), Some(&other.targets), |a, b| {
+                a.covers(b, vpc) || b.covers(a, vpc)
+            })
+            && overlaps_any(self.filters.hosts.as_deref(), other.filters.hosts.as_deref(), |a, b| {
+                a.covers(b, vpc) || b.covers(a, vpc)
+            })
+            && overlaps_any(
+                self.filters.ports.as_deref(),
+                other.filters.ports.as_deref(),
+                |a, b| match (parse_ports(a), parse_ports(b)) {
+                    (Some(a), Some(b)) => a.0 <= b.1 && b.0 <= a.1,
+                    _ => a == b,
+                },
+            )
+            && overlaps_any(
+                self.filters.protocols.as_deref(),
+                other.filters.protocols.as_deref(),
+                |a, b| a.eq_ignore_ascii_case(b),
+            )
+    }
+}
+
+impl RuleHost {
+    /// Returns if this matches every host `other` matches. `vpc` is the VPC the rules
+    /// are for, whose subnets and instances are all matched by a `vpc` host.
+    fn covers(&self, other: &RuleHost, vpc: &str) -> bool {
+        match (self, other) {
+            (RuleHost::Vpc(name), RuleHost::Subnet(_) | RuleHost::Instance(_)) => name == vpc,
+            (RuleHost::IpNet(net), RuleHost::Ip(ip)) => match net.parse::<ipnetwork::IpNetwork>() {
+                Ok(net) => net.contains(*ip),
+                Err(_) => false,
+            },
+            (RuleHost::IpNet(a), RuleHost::IpNet(b)) => {
+                match (a.parse::<ipnetwork::IpNetwork>(), b.parse::<ipnetwork::IpNetwork>()) {
+                    (Ok(a), Ok(b)) => a.contains(b.network()) && a.contains(b.broadcast()),
+                    _ => a == b,
+                }
+            }
+            _ => self == other,
+        }
+    }
+}
+
+/// Returns if every item of `b` is covered by an item of `a`. A filter that is not set
+/// matches everything.
+fn covers_all<T>(a: Option<&[T]>, b: Option<&[T]>, covers: impl Fn(&T, &T) -> bool) -> bool {
+    match (a, b) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(a), Some(b)) => b.iter().all(|y| a.iter().any(|x| covers(x, y))),
+    }
+}
+
+/// Returns if some item of `a` overlaps with some item of `b`. A filter that is not
+/// set matches everything.
+fn overlaps_any<T>(a: Option<&[T]>, b: Option<&[T]>, overlaps: impl Fn(&T, &T) -> bool) -> bool {
+    match (a, b) {
+        (None, _) | (_, None) => true,
+        (Some(a), Some(b)) => a.iter().any(|x| b.iter().any(|y| overlaps(x, y))),
+    }
+}
+
+/// Parse a port range like "22" or "8000-8080" into its first and last port.
+fn parse_ports(s: &str) -> Option<(u16, u16)> {
+    match s.split_once('-') {
+        Some((first, last)) => Some((first.trim().parse().ok()?, last.trim().parse().ok()?)),
+        None => {
+            let port = s.trim().parse().ok()?;
+            Some((port, port))
+        }
+    }
+}
+
+/// Get the current firewall rules of the VPC.
+async fn get_rules(client: &oxide_api::Client, organization: &str, project: &str, vpc: &str) -> Result<FirewallRules> {
+    let rules = client.vpcs().firewall_rules_get(organization, project, vpc).await?;
+
+    // The rules the API returns also have their IDs and timestamps, which we leave out
+    // so that they can be applied again as is.
+    Ok(serde_json::from_value(serde_json::to_value(rules)?)?)
+}
+
+/// Read firewall rules from a file, or stdin for "-", and make sure they can be applied.
+fn read_rules_file(ctx: &mut crate::context::Context, file: &str) -> Result<FirewallRules> {
+    let mut buf = String::new();
+    if file == "-" {
+        ctx.io.stdin.read_to_string(&mut buf)?;
+    } else {
+        std::fs::File::open(file)
+            .map_err(|err| anyhow!("failed to open {}: {}", file, err))?
+            .read_to_string(&mut buf)?;
+    }
+
+    // JSON is valid YAML, so this handles both.
+    let rules: FirewallRules =
+        serde_yaml::from_str(&buf).map_err(|err| anyhow!("failed to parse firewall rules from {}: {}", file, err))?;
+
+    let mut names: Vec<&str> = rules.rules.iter().map(|r| r.name.as_str()).collect();
+    names.sort_unstable();
+    if let Some(name) = names.windows(2).find(|w| w[0] == w[1]).map(|w| w[0]) {
+        return Err(anyhow!("firewall rule {} is defined more than once in {}", name, file));
+    }
+
+    Ok(rules)
+}
+
+/// The firewall rules of a VPC, as they are exported and applied.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct FirewallRules {
+    pub rules: Vec<FirewallRule>,
+}
+
+/// A firewall rule, without the fields the API sets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FirewallRule {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub status: RuleStatus,
+    pub direction: RuleDirection,
+    pub action: RuleAction,
+    pub priority: u16,
+    pub targets: Vec<RuleHost>,
+    #[serde(default)]
+    pub filters: RuleFilters,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "snake_case")]
+#[display(style = "snake_case")]
+pub enum RuleStatus {
+    Enabled,
+    Disabled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "snake_case")]
+#[display(style = "snake_case")]
+pub enum RuleDirection {
+    Inbound,
+    Outbound,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "snake_case")]
+#[display(style = "snake_case")]
+pub enum RuleAction {
+    Allow,
+    Deny,
+}
+
+/// A target of a rule, or a host its filters match.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum RuleHost {
+    Vpc(String),
+    Subnet(String),
+    Instance(String),
+    Ip(std::net::IpAddr),
+    IpNet(String),
+}
+
+impl std::fmt::Display for RuleHost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleHost::Vpc(name) => write!(f, "vpc:{}", name),
+            RuleHost::Subnet(name) => write!(f, "subnet:{}", name),
+            RuleHost::Instance(name) => write!(f, "instance:{}", name),
+            RuleHost::Ip(ip) => write!(f, "ip:{}", ip),
+            RuleHost::IpNet(net) => write!(f, "ip_net:{}", net),
+        }
+    }
+}
+
+/// The filters of a rule. A filter that is not set matches everything.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RuleFilters {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hosts: Option<Vec<RuleHost>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ports: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocols: Option<Vec<String>>,
+}
+
+/// How a rule differs between the current and the desired rules.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleDiff {
+    Added(FirewallRule),
+    Removed(FirewallRule),
+    /// The rule exists in both, with the fields that differ and their current and
+    /// desired values.
+    Changed {
+        name: String,
+        fields: Vec<(String, serde_json::Value, serde_json::Value)>,
+    },
+    Unchanged(String),
+}
+
+/// Compare the rules by name. The rules are returned in the order of the desired rules,
+/// followed by the rules that are removed.
+pub fn diff_rules(current: &FirewallRules, desired: &FirewallRules) -> Vec<RuleDiff> {
+    let mut diff = Vec::new();
+
+    for rule in &desired.rules {
+        let existing = match current.rules.iter().find(|r| r.name == rule.name) {
+            Some(existing) => existing,
+            None => {
+                diff.push(RuleDiff::Added(rule.clone()));
+                continue;
+            }
+        };
+
+        if existing == rule {
+            diff.push(RuleDiff::Unchanged(rule.name.to_string()));
+            continue;
+        }
+
+        let before = serde_json::to_value(existing).unwrap_or_default();
+        let after = serde_json::to_value(rule).unwrap_or_default();
+        let mut keys: Vec<&String> = before
+            .as_object()
+            .into_iter()
+            .chain(after.as_object())
+            .flat_map(|o| o.keys())
+            .collect();
+        keys.sort();
+        keys.dedup();
+
+        let fields = keys
+            .into_iter()
+            .filter(|k| before.get(k.as_str()) != after.get(k.as_str()))
+            .map(|k| {
+                (
+                    k.to_string(),
+                    before.get(k.as_str()).cloned().unwrap_or_default(),
+                    after.get(k.as_str()).cloned().unwrap_or_default(),
+                )
+            })
+            .collect();
+
+        diff.push(RuleDiff::Changed {
+            name: rule.name.to_string(),
+            fields,
+        });
+    }
+
+    for rule in &current.rules {
+        if !desired.rules.iter().any(|r| r.name == rule.name) {
+            diff.push(RuleDiff::Removed(rule.clone()));
+        }
+    }
+
+    diff
+}
+
+/// Print the changes to the rules, marking the rules that are added, removed, and
+/// changed.
+fn write_rules_diff(ctx: &mut crate::context::Context, diff: &[RuleDiff]) -> Result<()> {
+    let cs = ctx.io.color_scheme();
+    for d in diff {
+        match d {
+            RuleDiff::Added(rule) => {
+                writeln!(ctx.io.out, "{}", cs.green(&format!("+ {}", rule_summary(rule))))?;
+            }
+            RuleDiff::Removed(rule) => {
+                writeln!(ctx.io.out, "{}", cs.red(&format!("- {}", rule_summary(rule))))?;
+            }
+            RuleDiff::Changed { name, fields } => {
+                writeln!(ctx.io.out, "~ {}", cs.bold(name))?;
+                for (field, before, after) in fields {
+                    writeln!(ctx.io.out, "    {}:", field)?;
+                    writeln!(ctx.io.out, "{}", cs.red(&format!("    - {}", before)))?;
+                    writeln!(ctx.io.out, "{}", cs.green(&format!("    + {}", after)))?;
+                }
+            }
+            RuleDiff::Unchanged(name) => {
+                writeln!(ctx.io.out, "  {}", name)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A one line summary of a rule.
+fn rule_summary(rule: &FirewallRule) -> String {
+    format!(
+        "{} ({} {} priority {}, {})",
+        rule.name, rule.direction, rule.action, rule.priority, rule.status
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::cmd::Command;
+
+    pub struct TestItem {
+        name: String,
+        cmd: crate::cmd_vpc_firewall::SubCommand,
+        stdin: String,
+        want_out: String,
+        want_err: String,
+    }
+
+    const RULES: &str = r#"rules:
+  - name: allow-ssh
+    description: allow inbound ssh
+    status: enabled
+    direction: inbound
+    action: allow
+    priority: 65534
+    targets:
+      - type: vpc
+        value: default
+    filters:
+      ports:
+        - "22"
+      protocols:
+        - TCP
+  - name: allow-ssh
+    status: enabled
+    direction: inbound
+    action: allow
+    priority: 100
+    targets:
+      - type: vpc
+        value: default
+"#;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_cmd_vpc_firewall() {
+        let tests: Vec<TestItem> = vec![
+            TestItem {
+                name: "apply no --confirm non-interactive".to_string(),
+                cmd: crate::cmd_vpc_firewall::SubCommand::Apply(crate::cmd_vpc_firewall::CmdVpcFirewallApply {
+                    vpc: "default".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    file: "-".to_string(),
+                    dry_run: false,
+                    confirm: false,
+                }),
+
+                stdin: "".to_string(),
+                want_out: "".to_string(),
+                want_err: "--confirm required when not running interactively".to_string(),
+            },
+            TestItem {
+                name: "apply duplicate rule".to_string(),
+                cmd: crate::cmd_vpc_firewall::SubCommand::Apply(crate::cmd_vpc_firewall::CmdVpcFirewallApply {
+                    vpc: "default".to_string(),
+                    organization: "foo".to_string(),
+                    project: "bar".to_string(),
+                    file: "-".to_string(),
+                    dry_run: true,
+                    confirm: false,
+                }),
+
+                stdin: RULES.to_string(),
+                want_out: "".to_string(),
+                want_err: "firewall rule allow-ssh is defined more than once in -".to_string(),
+            },
+        ];
+
+        let mut config = crate::config::new_blank_config().unwrap();
+        let mut c = crate::config_from_env::EnvConfig::inherit_env(&mut config);
+
+        for t in tests {
+            let (mut io, stdout_path, stderr_path) = crate::iostreams::IoStreams::test();
+            if !t.stdin.is_empty() {
+                io.stdin = Box::new(std::io::Cursor::new(t.stdin));
+            }
+            // We need to also turn off the fancy terminal colors.
+            // This ensures it also works in GitHub actions/any CI.
+            io.set_color_enabled(false);
+            io.set_never_prompt(true);
+            let mut ctx = crate::context::Context {
+                config: &mut c,
+                io,
+                debug: false,
+            };
+
+            let cmd_vpc_firewall = crate::cmd_vpc_firewall::CmdVpcFirewall { subcmd: t.cmd };
+            match cmd_vpc_firewall.run(&mut ctx).await {
+                Ok(()) => {
+                    let stdout = std::fs::read_to_string(stdout_path).unwrap();
+                    let stderr = std::fs::read_to_string(stderr_path).unwrap();
+                    assert!(stderr.is_empty(), "test {}: {}", t.name, stderr);
+                    if !stdout.contains(&t.want_out) {
+                        assert_eq!(stdout, t.want_out, "test {}: stdout mismatch", t.name);
+                    }
+                }
+                Err(err) => {
+                    let stdout = std::fs::read_to_string(stdout_path).unwrap();
+                    let stderr = std::fs::read_to_string(stderr_path).unwrap();
+                    assert_eq!(stdout, t.want_out, "test {}", t.name);
+                    if !err.to_string().contains(&t.want_err) {
+                        assert_eq!(err.to_string(), t.want_err, "test {}: err mismatch", t.name);
+                    }
+                    assert!(stderr.is_empty(), "test {}: {}", t.name, stderr);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lint_rules() {
+        let rules: crate::cmd_vpc_firewall::FirewallRules = serde_yaml::from_str(
+            r#"rules:
+  - name: allow-web
+    status: enabled
+    direction: inbound
+    action: allow
+    priority: 100
+    targets:
+      - type: vpc
+        value: default
+    filters:
+      ports:
+        - "80-443"
+      protocols:
+        - TCP
+  - name: allow-https
+    status: enabled
+    direction: inbound
+    action: allow
+    priority: 200
+    targets:
+      - type: subnet
+        value: web
+    filters:
+      ports:
+        - "443"
+      protocols:
+        - TCP
+  - name: deny-office
+    status: enabled
+    direction: inbound
+    action: deny
+    priority: 300
+    targets:
+      - type: instance
+        value: db
+    filters:
+      hosts:
+        - type: ip_net
+          value: 10.0.0.0/8
+      ports:
+        - "400-500"
+  - name: allow-old
+    status: enabled
+    direction: outbound
+    action: allow
+    priority: 100
+    targets:
+      - type: subnet
+        value: gone
+  - name: deny-disabled
+    status: disabled
+    direction: inbound
+    action: deny
+    priority: 1
+    targets:
+      - type: vpc
+        value: default
+"#,
+        )
+        .unwrap();
+
+        let findings =
+            crate::cmd_vpc_firewall::lint_rules(&rules, "default", &["web".to_string()], &["db".to_string()]);
+
+        assert_eq!(
+            findings,
+            vec![
+                crate::cmd_vpc_firewall::LintFinding {
+                    rule: "allow-https".to_string(),
+                    message: "never matches, all of its traffic is matched first by allow rule allow-web (priority 100)"
+                        .to_string(),
+                },
+                crate::cmd_vpc_firewall::LintFinding {
+                    rule: "deny-office".to_string(),
+                    message: "matches some of the same traffic as allow rule allow-web (priority 100), which takes precedence"
+                        .to_string(),
+                },
+                crate::cmd_vpc_firewall::LintFinding {
+                    rule: "allow-old".to_string(),
+                    message: "target subnet gone does not exist in VPC default".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_rules() {
+        let rules: crate::cmd_vpc_firewall::FirewallRules = serde_yaml::from_str(RULES).unwrap();
+
+        let ssh = rules.rules[0].clone();
+        let mut icmp = ssh.clone();
+        icmp.name = "allow-icmp".to_string();
+        icmp.filters.ports = None;
+        icmp.filters.protocols = Some(vec!["ICMP".to_string()]);
+        let mut http = ssh.clone();
+        http.name = "allow-http".to_string();
+        http.filters.ports = Some(vec!["80".to_string()]);
+
+        let current = crate::cmd_vpc_firewall::FirewallRules {
+            rules: vec![ssh.clone(), icmp.clone(), http.clone()],
+        };
+
+        let mut ssh_disabled = ssh.clone();
+        ssh_disabled.status = crate::cmd_vpc_firewall::RuleStatus::Disabled;
+        let mut https = http.clone();
+        https.name = "allow-https".to_string();
+        https.filters.ports = Some(vec!["443".to_string()]);
+
+        let desired = crate::cmd_vpc_firewall::FirewallRules {
+            rules: vec![ssh_disabled, https.clone(), icmp],
+        };
+
+        assert_eq!(
+            crate::cmd_vpc_firewall::diff_rules(&current, &desired),
+            vec![
+                crate::cmd_vpc_firewall::RuleDiff::Changed {
+                    name: "allow-ssh".to_string(),
+                    fields: vec![(
+                        "status".to_string(),
+                        serde_json::json!("enabled"),
+                        serde_json::json!("disabled")
+                    )],
+                },
+                crate::cmd_vpc_firewall::RuleDiff::Added(https),
+                crate::cmd_vpc_firewall::RuleDiff::Unchanged("allow-icmp".to_string()),
+                crate::cmd_vpc_firewall::RuleDiff::Removed(http),
+            ]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::io::Write;
+
+use anyhow::Result;
+use clap::Parser;
+use serde::Serialize;
+
+use crate::cmd_policy::PolicyScope;
+
+/// Show who you are logged in as and the roles you hold.
+///
+/// The roles are read from the policies of the fleet and the current silo, and of
+/// the organization and project when `--organization` and `--project` are given.
+/// Only roles granted to you directly are listed, not those granted to groups you
+/// are a member of. Policies you are not allowed to read are shown as such.
+#[derive(Parser, Debug, Clone)]
+#[clap(verbatim_doc_comment)]
+pub struct CmdWhoami {
+    /// Also list the roles held on the organization.
+    #[clap(long, short, env = "OXIDE_ORG")]
+    pub organization: Option<String>,
+
+    /// Also list the roles held on the project.
+    #[clap(long, short, requires = "organization")]
+    pub project: Option<String>,
+
+    /// Display output in json, yaml, or table format.
+    #[clap(long, short)]
+    pub format: Option<crate::types::FormatOutput>,
+}
+
+/// The identity of the logged in user.
+#[derive(Debug, Clone, Serialize)]
+pub struct Identity {
+    pub host: String,
+    pub user: oxide_api::types::User,
+    pub token_source: String,
+    pub roles: Vec<ScopeRoles>,
+}
+
+/// The roles held on a resource.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScopeRoles {
+    /// The kind of resource, `fleet`, `silo`, `organization` or `project`.
+    pub scope: String,
+    /// The name of the resource, empty for the fleet and the current silo.
+    pub name: String,
+    /// The roles held, or `None` if the policy could not be read.
+    pub roles: Option<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl crate::cmd::Command for CmdWhoami {
+    async fn run(&self, ctx: &mut crate::context::Context) -> Result<()> {
+        let host = ctx.config.default_host()?;
+        let (_, token_source) = ctx.config.get_with_source(&host, "token")?;
+
+        let client = ctx.api_client("")?;
+        let user = client.hidden().session_me().await?;
+
+        let mut scopes = vec![PolicyScope::Global, PolicyScope::Silo(None)];
+        if let Some(organization) = &self.organization {
+            scopes.push(PolicyScope::Organization(organization.to_string()));
+            if let Some(project) = &self.project {
+                scopes.push(PolicyScope::Project {
+                    organization: organization.to_string(),
+                    project: project.to_string(),
+                });
+            }
+        }
+
+        let mut roles = Vec::new();
+        for scope in scopes {
+            let held = match scope.get(&client).await {
+                Ok(policy) => Some(user_roles(&policy, &user.id)),
+                Err(err) if is_forbidden(&err) => None,
+                Err(err) => return Err(err),
+            };
+
+            let name = match &scope {
+                PolicyScope::Global | PolicyScope::Silo(None) => String::new(),
+                PolicyScope::Silo(Some(silo)) => silo.to_string(),
+                PolicyScope::Organization(organization) => organization.to_string(),
+                PolicyScope::Project { organization, project } => format!("{}/{}", organization, project),
+            };
+
+            roles.push(ScopeRoles {
+                scope: scope.role_prefix().to_string(),
+                name,
+                roles: held,
+            });
+        }
+
+        let identity = Identity {
+            host,
+            user,
+            token_source,
+            roles,
+        };
+
+        let format = ctx.format(&self.format)?;
+        match format {
+            crate::types::FormatOutput::Json => ctx.io.write_output_json(&serde_json::to_value(&identity)?)?,
+            crate::types::FormatOutput::Yaml => ctx.io.write_output_yaml(&identity)?,
+            crate::types::FormatOutput::Table => write_identity(ctx, &identity)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Write the identity for people to read.
+fn write_identity(ctx: &mut crate::context::Context, identity: &Identity) -> Result<()> {
+    let cs = ctx.io.color_scheme();
+
+    writeln!(
+        ctx.io.out,
+        "{} Logged in to {} as {} ({})",
+        cs.success_icon(),
+        identity.host,
+        cs.bold(&crate::cmd_user::display_name(&identity.user)),
+        identity.user.id
+    )?;
+    writeln!(ctx.io.out, "Token from {}", identity.token_source)?;
+    writeln!(ctx.io.out)?;
+
+    for scope in &identity.roles {
+        let resource = if scope.name.is_empty() {
+            scope.scope.to_string()
+        } else {
+            format!("{} {}", scope.scope, scope.name)
+        };
+
+        let roles = match &scope.roles {
+            Some(roles) if roles.is_empty() => "no roles".to_string(),
+            Some(roles) => roles.join(", "),
+            None => cs.yellow("policy not readable"),
+        };
+
+        writeln!(ctx.io.out, "{}: {}", cs.bold(&resource), roles)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the roles granted directly to the user in the policy.
+fn user_roles(policy: &crate::cmd_policy::Policy, user_id: &str) -> Vec<String> {
+    let mut roles: Vec<String> = policy
+        .role_assignments
+        .iter()
+        .filter(|a| a.identity_type == "silo_user" && a.identity_id == user_id)
+        .map(|a| a.role_name.to_string())
+        .collect();
+    roles.sort();
+    roles.dedup();
+    roles
+}
+
+/// Returns if the error is the server saying we are not allowed to do something.
+fn is_forbidden(err: &anyhow::Error) -> bool {
+    crate::cmd::api_error_status(err) == Some(http::StatusCode::FORBIDDEN)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::cmd_policy::{Policy, RoleAssignment};
+
+    #[test]
+    fn test_user_roles() {
+        let assignment = |role: &str, identity_type: &str, id: &str| RoleAssignment {
+            role_name: role.to_string(),
+            identity_type: identity_type.to_string(),
+            identity_id: id.to_string(),
+        };
+
+        let policy = Policy {
+            role_assignments: vec![
+                assignment("viewer", "silo_user", "me"),
+                assignment("admin", "silo_user", "me"),
+                assignment("collaborator", "silo_user", "someone-else"),
+                assignment("collaborator", "silo_group", "me"),
+                assignment("viewer", "silo_user", "me"),
+            ],
+        };
+
+        assert_eq!(super::user_roles(&policy, "me"), vec!["admin", "viewer"]);
+        assert!(super::user_roles(&policy, "nobody").is_empty());
+    }
+}
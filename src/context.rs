@@ -46,24 +46,21 @@ impl Context<'_> {
             hostname.to_string()
         };
 
-        // Change the baseURL to the one we want.
-        let mut baseurl = host.to_string();
-        if !host.starts_with("http://") && !host.starts_with("https://") {
-            baseurl = format!("https://{}", host);
-            if host.starts_with("localhost") {
-                baseurl = format!("http://{}", host)
-            }
-        }
-
         // Get the token for that host.
         let token = self.config.get(&host, "token")?;
 
         // Create the client.
-        let client = oxide_api::Client::new(&token, &baseurl);
+        let client = oxide_api::Client::new(&token, &base_url(&host));
 
         Ok(client)
     }
 
+    /// This function returns an API client for Oxide that is not authenticated, for
+    /// logging in to the host with.
+    pub fn anonymous_api_client(&self, hostname: &str) -> oxide_api::Client {
+        oxide_api::Client::new("", &base_url(hostname))
+    }
+
     /// This function opens a browser that is based on the configured
     /// environment to the specified path.
     ///
@@ -101,6 +98,26 @@ impl Context<'_> {
         Ok(())
     }
 
+    /// Returns the command to run to edit text.
+    ///
+    /// Editor precedence:
+    /// 1. editor from config (or OXIDE_EDITOR)
+    /// 2. VISUAL
+    /// 3. EDITOR
+    /// 4. vi
+    pub fn editor(&self) -> String {
+        let editor = self.config.get("", "editor").unwrap_or_else(|_| "".to_string());
+        if !editor.is_empty() {
+            editor
+        } else if !get_env_var("VISUAL").is_empty() {
+            get_env_var("VISUAL")
+        } else if !get_env_var("EDITOR").is_empty() {
+            get_env_var("EDITOR")
+        } else {
+            "vi".to_string()
+        }
+    }
+
     /// Return the configured output format or override the default with the value passed in,
     /// if it is some.
     pub fn format(&self, format: &Option<FormatOutput>) -> Result<FormatOutput> {
@@ -113,6 +130,18 @@ impl Context<'_> {
     }
 }
 
+/// Returns the base URL of the API on the host, assuming https unless the host is
+/// localhost.
+fn base_url(host: &str) -> String {
+    if host.starts_with("http://") || host.starts_with("https://") {
+        host.to_string()
+    } else if host.starts_with("localhost") {
+        format!("http://{}", host)
+    } else {
+        format!("https://{}", host)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
@@ -0,0 +1,150 @@
+use std::io::Read;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+/// Returns the edited version of a resource, read from `from_file` if it is set, or
+/// edited as YAML in the configured editor otherwise. A `from_file` of `-` reads from
+/// standard input.
+///
+/// Both YAML and JSON are accepted, since YAML is a superset of JSON.
+pub fn read_edited<T: Serialize>(
+    ctx: &mut crate::context::Context,
+    current: &T,
+    from_file: &str,
+) -> Result<serde_json::Value> {
+    let contents = if from_file == "-" {
+        let mut contents = String::new();
+        ctx.io.stdin.read_to_string(&mut contents)?;
+        contents
+    } else if !from_file.is_empty() {
+        std::fs::read_to_string(from_file).map_err(|err| anyhow!("reading {} failed: {}", from_file, err))?
+    } else {
+        edit_in_editor(&ctx.editor(), &serde_yaml::to_string(current)?)?
+    };
+
+    serde_yaml::from_str(&contents).map_err(|err| anyhow!("parsing the edited resource failed: {}", err))
+}
+
+/// Write the text to a temporary file, open it in the editor, and return what it holds
+/// once the editor exits.
+fn edit_in_editor(editor: &str, text: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(format!("oxide-edit-{}.yaml", uuid::Uuid::new_v4()));
+    std::fs::write(&path, text)?;
+
+    let result = run_editor(editor, &path).and_then(|()| Ok(std::fs::read_to_string(&path)?));
+
+    // Clean up after ourselves, whether the edit worked or not.
+    let _ = std::fs::remove_file(&path);
+
+    result
+}
+
+/// Run the editor on the file and wait for it to exit.
+fn run_editor(editor: &str, path: &std::path::Path) -> Result<()> {
+    let mut args = shlex::split(editor).unwrap_or_default();
+    if args.is_empty() {
+        return Err(anyhow!("invalid editor command: {}", editor));
+    }
+    let program = args.remove(0);
+
+    let status = std::process::Command::new(&program)
+        .args(&args)
+        .arg(path)
+        .status()
+        .map_err(|err| anyhow!("running editor {} failed: {}", editor, err))?;
+    if !status.success() {
+        return Err(anyhow!("editor {} exited with {}", editor, status));
+    }
+
+    Ok(())
+}
+
+/// Returns the names of the top-level fields of the edited resource whose values differ
+/// from the current resource.
+///
+/// Fields left out of the edited resource are taken to be unchanged, and timestamps are
+/// ignored since only the system sets them. This lets a resource saved earlier be passed
+/// back in without its stale timestamps counting as edits.
+pub fn changed_fields(current: &serde_json::Value, edited: &serde_json::Value) -> Result<Vec<String>> {
+    let edited = edited
+        .as_object()
+        .ok_or_else(|| anyhow!("the edited resource must be an object, not {}", edited))?;
+
+    let mut changed = edited
+        .iter()
+        .filter(|(field, _)| !field.starts_with("time_"))
+        .filter(|(field, value)| current.get(field.as_str()) != Some(value))
+        .map(|(field, _)| field.to_string())
+        .collect::<Vec<_>>();
+    changed.sort();
+
+    Ok(changed)
+}
+
+/// Returns an error naming the changed fields that cannot be edited, if there are any.
+///
+/// This is checked before any change is applied, so an edit is either applied as a
+/// whole or not at all.
+pub fn refuse_uneditable(kind: &str, changed: &[String], editable: &[&str]) -> Result<()> {
+    let refused = changed
+        .iter()
+        .filter(|field| !editable.contains(&field.as_str()))
+        .map(|field| field.as_str())
+        .collect::<Vec<_>>();
+    if refused.is_empty() {
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "the API does not allow editing the {} of the {}, only its {}",
+        refused.join(", "),
+        kind,
+        editable.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_changed_fields() {
+        let current = serde_json::json!({
+            "name": "things",
+            "description": "some things",
+            "run_state": "running",
+            "time_modified": "2022-08-01T12:00:00Z",
+        });
+
+        let edited = serde_json::json!({
+            "name": "things",
+            "description": "other things",
+            "run_state": "stopped",
+            "time_modified": "2022-07-01T12:00:00Z",
+        });
+        assert_eq!(
+            super::changed_fields(&current, &edited).unwrap(),
+            vec!["description", "run_state"]
+        );
+
+        let edited = serde_json::json!({ "run_state": "running" });
+        assert!(super::changed_fields(&current, &edited).unwrap().is_empty());
+
+        let err = super::changed_fields(&current, &serde_json::json!("things")).unwrap_err();
+        assert_eq!(err.to_string(), "the edited resource must be an object, not \"things\"");
+    }
+
+    #[test]
+    fn test_refuse_uneditable() {
+        let changed = vec!["description".to_string(), "name".to_string(), "run_state".to_string()];
+
+        assert!(super::refuse_uneditable("instance", &changed[2..], &["run_state"]).is_ok());
+
+        let err = super::refuse_uneditable("instance", &changed, &["run_state"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the API does not allow editing the description, name of the instance, only its run_state"
+        );
+    }
+}
@@ -19,40 +19,68 @@ pub mod cmd_config;
 pub mod cmd_disk;
 /// The generate command.
 pub mod cmd_generate;
+/// The id command.
+pub mod cmd_id;
 /// The image command.
 pub mod cmd_image;
 /// The image global subcommand.
 pub mod cmd_image_global;
 /// The instance command.
 pub mod cmd_instance;
+/// The instance nic subcommand.
+pub mod cmd_instance_nic;
+/// The ip-pool command.
+pub mod cmd_ip_pool;
+/// The metrics command.
+pub mod cmd_metrics;
 /// The open command.
 pub mod cmd_open;
 /// The organization command.
 pub mod cmd_org;
+/// The policy command.
+pub mod cmd_policy;
 /// The project command.
 pub mod cmd_project;
 /// The rack command.
 pub mod cmd_rack;
+/// The rack ip-pool subcommand.
+pub mod cmd_rack_ip_pool;
 /// The role command.
 pub mod cmd_role;
 /// The route command.
 pub mod cmd_route;
 /// The router command.
 pub mod cmd_router;
+/// The saga command.
+pub mod cmd_saga;
+/// The silo command.
+pub mod cmd_silo;
 /// The sled command.
 pub mod cmd_sled;
 /// The snapshot command.
 pub mod cmd_snapshot;
+/// The ssh-config command.
+pub mod cmd_ssh_config;
 /// The ssh-key command.
 pub mod cmd_ssh_key;
 /// The subnet command.
 pub mod cmd_subnet;
+/// The system command.
+pub mod cmd_system;
+/// The system update subcommand.
+pub mod cmd_system_update;
 /// The update command.
 pub mod cmd_update;
+/// The user command.
+pub mod cmd_user;
 /// The version command.
 pub mod cmd_version;
 /// The vpc command.
 pub mod cmd_vpc;
+/// The vpc firewall subcommand.
+pub mod cmd_vpc_firewall;
+/// The whoami command.
+pub mod cmd_whoami;
 
 // Use of a mod or pub mod is not actually necessary.
 mod built_info {
@@ -70,6 +98,7 @@ mod config_map;
 mod context;
 mod docs_man;
 mod docs_markdown;
+mod edit;
 mod iostreams;
 mod prompt_ext;
 mod types;
@@ -153,14 +182,20 @@ enum SubCommand {
     #[clap(alias = "disks")]
     Disk(cmd_disk::CmdDisk),
     Generate(cmd_generate::CmdGenerate),
+    Id(cmd_id::CmdId),
     #[clap(alias = "images")]
     Image(cmd_image::CmdImage),
     #[clap(alias = "instances")]
     Instance(cmd_instance::CmdInstance),
+    #[clap(alias = "ip-pools")]
+    IpPool(cmd_ip_pool::CmdIpPool),
+    Metrics(cmd_metrics::CmdMetrics),
     #[clap(alias = "open")]
     Open(cmd_open::CmdOpen),
     #[clap(alias = "orgs")]
     Org(cmd_org::CmdOrganization),
+    #[clap(alias = "policies")]
+    Policy(cmd_policy::CmdPolicy),
     #[clap(alias = "projects")]
     Project(cmd_project::CmdProject),
     #[clap(alias = "racks")]
@@ -171,18 +206,27 @@ enum SubCommand {
     Route(cmd_route::CmdRoute),
     #[clap(alias = "routers")]
     Router(cmd_router::CmdRouter),
+    #[clap(alias = "sagas")]
+    Saga(cmd_saga::CmdSaga),
+    #[clap(alias = "silos")]
+    Silo(cmd_silo::CmdSilo),
     #[clap(alias = "sleds")]
     Sled(cmd_sled::CmdSled),
     #[clap(alias = "snapshots")]
     Snapshot(cmd_snapshot::CmdSnapshot),
+    SshConfig(cmd_ssh_config::CmdSshConfig),
     #[clap(alias = "ssh-keys")]
     SshKey(cmd_ssh_key::CmdSSHKey),
     #[clap(alias = "subnets")]
     Subnet(cmd_subnet::CmdSubnet),
+    System(cmd_system::CmdSystem),
     Update(cmd_update::CmdUpdate),
+    #[clap(alias = "users")]
+    User(cmd_user::CmdUser),
     Version(cmd_version::CmdVersion),
     #[clap(alias = "vpcs")]
     Vpc(cmd_vpc::CmdVpc),
+    Whoami(cmd_whoami::CmdWhoami),
 }
 
 #[tokio::main]
@@ -293,22 +337,32 @@ async fn do_main(mut args: Vec<String>, ctx: &mut crate::context::Context<'_>) -
         SubCommand::Config(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Disk(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Generate(cmd) => run_cmd(&cmd, ctx).await,
+        SubCommand::Id(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Image(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Instance(cmd) => run_cmd(&cmd, ctx).await,
+        SubCommand::IpPool(cmd) => run_cmd(&cmd, ctx).await,
+        SubCommand::Metrics(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Open(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Org(cmd) => run_cmd(&cmd, ctx).await,
+        SubCommand::Policy(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Project(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Rack(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Role(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Route(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Router(cmd) => run_cmd(&cmd, ctx).await,
+        SubCommand::Saga(cmd) => run_cmd(&cmd, ctx).await,
+        SubCommand::Silo(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Sled(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Snapshot(cmd) => run_cmd(&cmd, ctx).await,
+        SubCommand::SshConfig(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::SshKey(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Subnet(cmd) => run_cmd(&cmd, ctx).await,
+        SubCommand::System(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Update(cmd) => run_cmd(&cmd, ctx).await,
+        SubCommand::User(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Version(cmd) => run_cmd(&cmd, ctx).await,
         SubCommand::Vpc(cmd) => run_cmd(&cmd, ctx).await,
+        SubCommand::Whoami(cmd) => run_cmd(&cmd, ctx).await,
     }
 }
 
@@ -19,3 +19,137 @@ impl FormatOutput {
         vec!["table".to_string(), "json".to_string(), "yaml".to_string()]
     }
 }
+
+/// A point in time given on the command line, either as `now`, an RFC 3339
+/// timestamp, or a duration before now like `30m` or `1d12h`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeSpec {
+    Now,
+    At(chrono::DateTime<chrono::Utc>),
+    Ago(chrono::Duration),
+}
+
+impl TimeSpec {
+    /// Returns the point in time, relative to `now`.
+    pub fn resolve(&self, now: chrono::DateTime<chrono::Utc>) -> anyhow::Result<chrono::DateTime<chrono::Utc>> {
+        match self {
+            TimeSpec::Now => Ok(now),
+            TimeSpec::At(t) => Ok(*t),
+            TimeSpec::Ago(d) => now
+                .checked_sub_signed(*d)
+                .ok_or_else(|| anyhow::anyhow!("time {} ago is out of range", self)),
+        }
+    }
+}
+
+impl std::str::FromStr for TimeSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if s == "now" {
+            return Ok(TimeSpec::Now);
+        }
+
+        if let Ok(t) = chrono::DateTime::parse_from_rfc3339(s) {
+            return Ok(TimeSpec::At(t.with_timezone(&chrono::Utc)));
+        }
+
+        // Parse durations like `90s`, `1h`, or `1d12h`.
+        let invalid = || {
+            anyhow::anyhow!(
+                "invalid time `{}`, expected `now`, an RFC 3339 timestamp, or a duration like `1h`",
+                s
+            )
+        };
+        let mut total = chrono::Duration::zero();
+        let mut digits = String::new();
+        for c in s.chars() {
+            if c.is_ascii_digit() {
+                digits.push(c);
+                continue;
+            }
+
+            let n: i64 = digits.parse().map_err(|_| invalid())?;
+            digits.clear();
+
+            let unit = match c {
+                's' => 1,
+                'm' => 60,
+                'h' => 60 * 60,
+                'd' => 24 * 60 * 60,
+                'w' => 7 * 24 * 60 * 60,
+                _ => return Err(invalid()),
+            };
+
+            // Durations are kept in milliseconds, and building one that doesn't fit
+            // panics, so check the bounds first.
+            let secs = n.checked_mul(unit).ok_or_else(invalid)?;
+            if secs > i64::MAX / 1000 {
+                return Err(invalid());
+            }
+            total = total
+                .checked_add(&chrono::Duration::seconds(secs))
+                .ok_or_else(invalid)?;
+        }
+
+        // A trailing number without a unit, or nothing at all.
+        if !digits.is_empty() || total.is_zero() {
+            return Err(invalid());
+        }
+
+        Ok(TimeSpec::Ago(total))
+    }
+}
+
+impl std::fmt::Display for TimeSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeSpec::Now => write!(f, "now"),
+            TimeSpec::At(t) => write!(f, "{}", t.to_rfc3339()),
+            TimeSpec::Ago(d) => write!(f, "{}s", d.num_seconds()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_time_spec_parse() {
+        assert_eq!(TimeSpec::from_str("now").unwrap(), TimeSpec::Now);
+        assert_eq!(
+            TimeSpec::from_str("1d12h").unwrap(),
+            TimeSpec::Ago(chrono::Duration::hours(36))
+        );
+        assert_eq!(
+            TimeSpec::from_str("90s").unwrap(),
+            TimeSpec::Ago(chrono::Duration::seconds(90))
+        );
+
+        let now = chrono::Utc::now();
+        let t = TimeSpec::from_str("2022-08-01T12:00:00Z").unwrap();
+        assert_eq!(t.resolve(now).unwrap().to_rfc3339(), "2022-08-01T12:00:00+00:00");
+        assert_eq!(
+            TimeSpec::from_str("30m").unwrap().resolve(now).unwrap(),
+            now - chrono::Duration::minutes(30)
+        );
+
+        assert!(TimeSpec::from_str("99999999999999w").is_err());
+        assert!(TimeSpec::from_str("9223372036854775807s").is_err());
+        assert!(TimeSpec::from_str("99999999999999999999s").is_err());
+        assert!(TimeSpec::from_str("9000000000000000s9000000000000000s").is_err());
+        assert!(TimeSpec::from_str("9000000000000000s").unwrap().resolve(now).is_err());
+
+        assert!(TimeSpec::from_str("").is_err());
+        assert!(TimeSpec::from_str("10").is_err());
+        assert!(TimeSpec::from_str("1y").is_err());
+        assert!(TimeSpec::from_str("yesterday").is_err());
+    }
+}